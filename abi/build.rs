@@ -27,6 +27,7 @@ fn main() {
     tonic_build::configure()
         .out_dir("src/pb")
         .with_sqlx_type(&["reservation.ReservationStatus"])
+        .with_serde(&["reservation.OpeningHours"])
         .with_derive_builder(&[
             "reservation.ReservationQuery",
             "reservation.ReservationFilter",
@@ -40,11 +41,18 @@ fn main() {
                 "page",
                 "page_size",
                 "desc",
+                "include_blackouts",
             ],
         )
         .with_derive_builder_into(
             "reservation.ReservationFilter",
-            &["resource_id", "user_id", "status", "desc"],
+            &[
+                "resource_id",
+                "user_id",
+                "status",
+                "desc",
+                "include_blackouts",
+            ],
         )
         .with_derive_builder_option("reservation.ReservationFilter", &["cursor"])
         .with_derive_builder_option("reservation.ReservationQuery", &["start", "end"])
@@ -72,6 +80,7 @@ fn main() {
 
 trait BuildExt {
     fn with_sqlx_type(self, paths: &[&str]) -> Self;
+    fn with_serde(self, paths: &[&str]) -> Self;
     fn with_derive_builder(self, paths: &[&str]) -> Self;
     fn with_derive_builder_into(self, path: &str, fields: &[&str]) -> Self;
     fn with_derive_builder_option(self, path: &str, fields: &[&str]) -> Self;
@@ -86,6 +95,12 @@ impl BuildExt for Builder {
         })
     }
 
+    fn with_serde(self, paths: &[&str]) -> Self {
+        paths.iter().fold(self, |acc, path| {
+            acc.type_attribute(path, "#[derive(serde::Serialize, serde::Deserialize)]")
+        })
+    }
+
     fn with_derive_builder(self, paths: &[&str]) -> Self {
         paths.iter().fold(self, |acc, path| {
             acc.type_attribute(path, "#[derive(derive_builder::Builder)]")
@@ -6,6 +6,8 @@ use std::{fs, path::Path};
 pub struct Config {
     pub db: DbConfig,
     pub server: ServerConfig,
+    #[serde(default)]
+    pub reaper: ReaperConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -27,6 +29,29 @@ fn default_pool_size() -> u32 {
 pub struct ServerConfig {
     pub host: String,
     pub port: u16,
+    /// bearer token of the admin rpcs (blackout), they are rejected if not set
+    #[serde(default)]
+    pub admin_token: Option<String>,
+}
+
+/// background task cancelling pending reservations whose hold has expired
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReaperConfig {
+    /// seconds between two runs, 0 disables the reaper
+    #[serde(default = "default_reap_interval")]
+    pub interval: u64,
+}
+
+fn default_reap_interval() -> u64 {
+    60
+}
+
+impl Default for ReaperConfig {
+    fn default() -> Self {
+        Self {
+            interval: default_reap_interval(),
+        }
+    }
 }
 
 impl Config {
@@ -84,9 +109,20 @@ mod test {
                 },
                 server: ServerConfig {
                     host: "0.0.0.0".to_string(),
-                    port: 50051
-                }
+                    port: 50051,
+                    admin_token: Some("admin-secret".to_string())
+                },
+                reaper: ReaperConfig { interval: 30 }
             }
         );
     }
+
+    #[test]
+    fn config_without_reaper_should_use_default() {
+        let config = Config::load("../reservation.yml").unwrap();
+
+        assert_eq!(config.reaper, ReaperConfig::default());
+        assert_eq!(config.reaper.interval, 60);
+        assert_eq!(config.server.admin_token, None);
+    }
 }
@@ -1,19 +1,29 @@
+use super::StatusDetail;
+use crate::{
+    convert_to_timestamp, convert_to_utc_time, ConflictingReservation, ReservationConflictDetail,
+    ReservationWindow,
+};
 use chrono::{DateTime, Utc};
-use regex::Regex;
 use std::{collections::HashMap, convert::Infallible, str::FromStr};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ReservationConflictInfo {
-    Parsed(ReservationConflict),
+    Parsed(Box<ReservationConflict>),
     Unparsed(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ReservationConflict {
     pub new: ResrvationWindow,
     pub old: ResrvationWindow,
+    /// reservations overlapping the new window, looked up after the conflict. empty if unknown
+    pub existing: Vec<ConflictingReservation>,
+    /// sub-windows of the new window in which the resource is fully booked, looked up after the
+    /// conflict as well
+    pub saturated: Vec<ResrvationWindow>,
 }
 
+/// an unbounded or infinite side of the window is DateTime::<Utc>::MIN_UTC / MAX_UTC
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResrvationWindow {
     pub rid: String,
@@ -26,7 +36,7 @@ impl FromStr for ReservationConflictInfo {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(conflict) = s.parse() {
-            Ok(Self::Parsed(conflict))
+            Ok(Self::Parsed(Box::new(conflict)))
         } else {
             Ok(Self::Unparsed(s.to_string()))
         }
@@ -48,6 +58,8 @@ impl TryFrom<ParsedInfo> for ReservationConflict {
         Ok(Self {
             new: value.new.try_into()?,
             old: value.old.try_into()?,
+            existing: vec![],
+            saturated: vec![],
         })
     }
 }
@@ -57,10 +69,14 @@ impl TryFrom<HashMap<String, String>> for ResrvationWindow {
 
     // "Key (resource_id, timespan)=(ocean-view-room-713, [\"2023-12-26 22:00:00+00\",\"2023-12-30 19:00:00+00\")) conflicts with existing key (resource_id, timespan)=(ocean-view-room-713, [\"2023-12-25 22:00:00+00\",\"2023-12-28 19:00:00+00\"))."
     fn try_from(value: HashMap<String, String>) -> Result<Self, Self::Error> {
-        let timespan_str = value.get("timespan").ok_or(())?.replace('"', "");
-        let mut split = timespan_str.splitn(2, ',');
-        let start = parse_datetime(split.next().ok_or(())?)?;
-        let end = parse_datetime(split.next().ok_or(())?)?;
+        // the exclusion is on the padded window since buffers were added
+        let timespan = value
+            .get("padded_timespan")
+            .or_else(|| value.get("timespan"))
+            .ok_or(())?;
+        let (start, end) = parse_bounds(timespan).ok_or(())?;
+        let start = parse_bound(start, DateTime::<Utc>::MIN_UTC)?;
+        let end = parse_bound(end, DateTime::<Utc>::MAX_UTC)?;
         Ok(Self {
             rid: value.get("resource_id").ok_or(())?.to_string(),
             start,
@@ -69,6 +85,75 @@ impl TryFrom<HashMap<String, String>> for ResrvationWindow {
     }
 }
 
+impl From<&ReservationConflictInfo> for ReservationConflictDetail {
+    fn from(info: &ReservationConflictInfo) -> Self {
+        match info {
+            ReservationConflictInfo::Parsed(conflict) => Self {
+                new: Some((&conflict.new).into()),
+                old: Some((&conflict.old).into()),
+                conflicting_id: conflict.existing.first().map_or(0, |v| v.id),
+                conflicting: conflict.existing.clone(),
+                saturated: conflict.saturated.iter().map(Into::into).collect(),
+                ..Default::default()
+            },
+            ReservationConflictInfo::Unparsed(detail) => Self {
+                unparsed: detail.clone(),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<&ResrvationWindow> for ReservationWindow {
+    fn from(window: &ResrvationWindow) -> Self {
+        Self {
+            resource_id: window.rid.clone(),
+            start: Some(convert_to_timestamp(&window.start)),
+            end: Some(convert_to_timestamp(&window.end)),
+        }
+    }
+}
+
+impl TryFrom<ReservationWindow> for ResrvationWindow {
+    type Error = ();
+
+    fn try_from(window: ReservationWindow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            rid: window.resource_id,
+            start: convert_to_utc_time(window.start.as_ref().ok_or(())?),
+            end: convert_to_utc_time(window.end.as_ref().ok_or(())?),
+        })
+    }
+}
+
+impl TryFrom<ReservationConflictDetail> for ReservationConflict {
+    type Error = ();
+
+    fn try_from(detail: ReservationConflictDetail) -> Result<Self, Self::Error> {
+        Ok(Self {
+            new: detail.new.ok_or(())?.try_into()?,
+            old: detail.old.ok_or(())?.try_into()?,
+            existing: detail.conflicting,
+            saturated: detail
+                .saturated
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl StatusDetail for ReservationConflictDetail {}
+
+impl ReservationConflict {
+    /// client side helper: decode the conflict from the status returned by the server
+    pub fn from_status(status: &tonic::Status) -> Option<Self> {
+        ReservationConflictDetail::from_status(status)?
+            .try_into()
+            .ok()
+    }
+}
+
 struct ParsedInfo {
     new: HashMap<String, String>,
     old: HashMap<String, String>,
@@ -77,38 +162,107 @@ struct ParsedInfo {
 impl FromStr for ParsedInfo {
     type Err = ();
 
+    // key values are printed verbatim, so a resource id may contain anything, even the separators
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        // use regular expression to parse the string
-        let re = Regex::new(r#"\((?P<k1>[a-zA-Z0-9_-]+),\s*(?P<k2>[a-zA-Z0-9_-]+)\)=\((?P<v1>[a-zA-Z0-9_-]+)\s*,\s*\[(?P<v2>[^\)]+)\)"#).unwrap();
-        let mut maps = vec![];
-        for cap in re.captures_iter(s) {
-            let mut map = HashMap::new();
-            map.insert(cap["k1"].to_string(), cap["v1"].to_string());
-            map.insert(cap["k2"].to_string(), cap["v2"].to_string());
-            maps.push(Some(map));
-        }
+        let rest = s.strip_prefix("Key (").ok_or(())?;
+        let (columns, rest) = rest.split_once(")=(").ok_or(())?;
+        let keys: Vec<&str> = columns.split(", ").collect();
+        let rest = rest.strip_suffix(").").ok_or(())?;
+
+        // take the first split where both sides look like key values
+        let separator = format!(") conflicts with existing key ({})=(", columns);
+        rest.match_indices(&separator)
+            .find_map(|(i, _)| {
+                Some(Self {
+                    new: parse_key_values(&keys, &rest[..i])?,
+                    old: parse_key_values(&keys, &rest[i + separator.len()..])?,
+                })
+            })
+            .ok_or(())
+    }
+}
+
+/// map "room-1, [\"..\",\"..\")" to the key columns. the last column is the range of the
+/// exclusion constraint, stored without its brackets. the range never contains ", ", so whatever
+/// comes before it belongs to the only other column
+fn parse_key_values(keys: &[&str], values: &str) -> Option<HashMap<String, String>> {
+    let (id, range) = match keys {
+        [_] => ("", values),
+        [_, _] => values.rsplit_once(", ")?,
+        _ => return None,
+    };
+    let inner = range.strip_prefix(['[', '('])?.strip_suffix([']', ')'])?;
+    parse_bounds(inner)?;
 
-        if maps.len() != 2 {
-            return Err(());
+    let mut map = HashMap::new();
+    if let [key, _] = keys {
+        map.insert(key.to_string(), id.to_string());
+    }
+    map.insert(keys.last()?.to_string(), inner.to_string());
+    Some(map)
+}
+
+/// split the inside of a range literal into its two bounds, unquoting them. empty bounds are None
+fn parse_bounds(s: &str) -> Option<(Option<String>, Option<String>)> {
+    let mut chars = s.chars().peekable();
+    let mut bounds = [None, None];
+    for (i, bound) in bounds.iter_mut().enumerate() {
+        let mut value = String::new();
+        let (mut quoted, mut in_quotes, mut more) = (false, false, false);
+        while let Some(c) = chars.next() {
+            match c {
+                ',' if !in_quotes => {
+                    more = true;
+                    break;
+                }
+                '"' if in_quotes && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    value.push('"');
+                }
+                '"' => {
+                    in_quotes = !in_quotes;
+                    quoted = true;
+                }
+                '\\' => value.push(chars.next()?),
+                c => value.push(c),
+            }
+        }
+        // exactly one comma between the bounds, and no dangling quote
+        if in_quotes || more != (i == 0) {
+            return None;
+        }
+        if quoted || !value.is_empty() {
+            *bound = Some(value);
         }
+    }
+    let [lower, upper] = bounds;
+    Some((lower, upper))
+}
 
-        Ok(Self {
-            new: maps[0].take().unwrap(),
-            old: maps[1].take().unwrap(),
-        })
+fn parse_bound(bound: Option<String>, unbounded: DateTime<Utc>) -> Result<DateTime<Utc>, ()> {
+    match bound.as_deref() {
+        None => Ok(unbounded),
+        Some("infinity") => Ok(DateTime::<Utc>::MAX_UTC),
+        Some("-infinity") => Ok(DateTime::<Utc>::MIN_UTC),
+        Some(s) => parse_datetime(s),
     }
 }
 
 fn parse_datetime(s: &str) -> Result<DateTime<Utc>, ()> {
-    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%#z")
+    Ok(DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z")
         .map_err(|_| ())?
         .with_timezone(&Utc))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ParsedInfo, ReservationConflictInfo};
-    use crate::error::ResrvationWindow;
+    use super::{ParsedInfo, ReservationConflictInfo, StatusDetail};
+    use crate::{
+        error::{ReservationConflict, ResrvationWindow},
+        ConflictingReservation, Error, ReservationConflictDetail, ReservationStatus,
+    };
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+    use proptest::prelude::*;
     use std::collections::HashMap;
 
     const ERR_MES: &str = "Key (resource_id, timespan)=(ocean-view-room-713, [\"2023-12-26 22:00:00+00\",\"2023-12-30 19:00:00+00\")) conflicts with existing key (resource_id, timespan)=(ocean-view-room-713, [\"2023-12-25 22:00:00+00\",\"2023-12-28 19:00:00+00\")).";
@@ -160,4 +314,171 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn conflict_with_unusual_resource_ids_should_parse() {
+        for rid in [
+            "lab/rack.3",
+            "会议室-7",
+            "room, (east)",
+            "a) conflicts with",
+        ] {
+            let s = ERR_MES.replace("ocean-view-room-713", rid);
+            let conflict: ReservationConflict = s.parse().unwrap();
+            assert_eq!(conflict.new.rid, rid);
+            assert_eq!(conflict.old.rid, rid);
+        }
+    }
+
+    #[test]
+    fn conflict_with_infinite_bounds_should_parse() {
+        let s = "Key (resource_id, timespan)=(room, [\"2023-12-26 22:00:00.25+05:30\",infinity]) conflicts with existing key (resource_id, timespan)=(room, (,\"2023-12-28 19:00:00+00\")).";
+        let conflict: ReservationConflict = s.parse().unwrap();
+        assert_eq!(
+            conflict.new.start.to_string(),
+            "2023-12-26 16:30:00.250 UTC"
+        );
+        assert_eq!(conflict.new.end, DateTime::<Utc>::MAX_UTC);
+        assert_eq!(conflict.old.start, DateTime::<Utc>::MIN_UTC);
+        assert_eq!(conflict.old.end.to_string(), "2023-12-28 19:00:00 UTC");
+    }
+
+    #[test]
+    fn conflict_on_padded_timespan_should_parse() {
+        let s = ERR_MES.replace("timespan)", "padded_timespan)");
+        let conflict: ReservationConflict = s.parse().unwrap();
+        let expected: ReservationConflict = ERR_MES.parse().unwrap();
+        assert_eq!(conflict, expected);
+    }
+
+    #[test]
+    fn malformed_conflict_should_be_unparsed() {
+        for s in [
+            "",
+            "Key (resource_id, timespan)=(room, [\"2023-12-26 22:00:00+00\")) conflicts with existing key (resource_id, timespan)=(room, [,)).",
+            "Key (resource_id, timespan)=(room, [\"2023-12-26 22:00:00+00,)) conflicts with existing key (resource_id, timespan)=(room, [,)).",
+            "Key (a, b, c)=(1, 2, [,)) conflicts with existing key (a, b, c)=(1, 2, [,)).",
+        ] {
+            let info: ReservationConflictInfo = s.parse().unwrap();
+            assert_eq!(info, ReservationConflictInfo::Unparsed(s.into()));
+        }
+    }
+
+    #[test]
+    fn conflict_should_round_trip_through_status() {
+        let info: ReservationConflictInfo = ERR_MES.parse().unwrap();
+        let status: tonic::Status = Error::ConflictReservation(info.clone()).into();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let detail = ReservationConflictDetail::from_status(&status).unwrap();
+        assert_eq!(
+            detail.new.as_ref().unwrap().resource_id,
+            "ocean-view-room-713"
+        );
+        assert!(detail.unparsed.is_empty());
+
+        let conflict = ReservationConflict::from_status(&status).unwrap();
+        assert_eq!(ReservationConflictInfo::Parsed(Box::new(conflict)), info);
+    }
+
+    #[test]
+    fn conflicting_reservations_should_round_trip_through_status() {
+        let mut conflict: ReservationConflict = ERR_MES.parse().unwrap();
+        conflict.existing = vec![ConflictingReservation {
+            id: 42,
+            user_id: "".into(),
+            status: ReservationStatus::Confirmed as i32,
+            resource_id: "ocean-view-room-713".into(),
+        }];
+        conflict.saturated = vec![conflict.old.clone()];
+        let info = ReservationConflictInfo::Parsed(Box::new(conflict.clone()));
+        let status: tonic::Status = Error::ConflictReservation(info).into();
+
+        let detail = ReservationConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.conflicting_id, 42);
+        assert_eq!(ReservationConflict::from_status(&status), Some(conflict));
+    }
+
+    #[test]
+    fn unparsed_conflict_should_keep_detail_message() {
+        let info = ReservationConflictInfo::Unparsed("unexpected".into());
+        let status: tonic::Status = Error::ConflictReservation(info).into();
+
+        let detail = ReservationConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.unparsed, "unexpected");
+        assert!(ReservationConflict::from_status(&status).is_none());
+        assert!(ReservationConflict::from_status(&tonic::Status::not_found("")).is_none());
+    }
+
+    /// a timestamp bound the way Postgres prints it: microseconds, trailing zeros trimmed
+    fn pg_bound(ts: Option<(i64, u32, i32)>) -> (String, Option<DateTime<Utc>>) {
+        let Some((secs, micros, offset)) = ts else {
+            return (String::new(), None);
+        };
+        let utc = Utc.timestamp_opt(secs, micros * 1000).unwrap();
+        let tz = FixedOffset::east_opt(offset).unwrap();
+        let mut s = utc
+            .with_timezone(&tz)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        if micros > 0 {
+            s.push_str(format!(".{:06}", micros).trim_end_matches('0'));
+        }
+        let sign = if offset < 0 { '-' } else { '+' };
+        s.push_str(&format!("{}{:02}", sign, offset.abs() / 3600));
+        if offset % 3600 != 0 {
+            s.push_str(&format!(":{:02}", offset.abs() % 3600 / 60));
+        }
+        (format!("\"{}\"", s), Some(utc))
+    }
+
+    fn pg_key(
+        rid: &str,
+        lower: &(String, Option<DateTime<Utc>>),
+        upper: &(String, Option<DateTime<Utc>>),
+    ) -> String {
+        format!(
+            "(resource_id, timespan)=({}, [{},{}))",
+            rid, lower.0, upper.0
+        )
+    }
+
+    fn timestamp() -> impl Strategy<Value = Option<(i64, u32, i32)>> {
+        // 0001-01-01 to 9999-12-31, offsets in quarter hours like real time zones
+        prop::option::of((
+            -62_135_596_800i64..253_402_214_400,
+            prop_oneof![Just(0u32), 0u32..1_000_000],
+            (-48i32..=56).prop_map(|q| q * 900),
+        ))
+    }
+
+    proptest! {
+        #[test]
+        fn conflict_parse_should_never_panic(s in ".*") {
+            let _ = s.parse::<ReservationConflictInfo>();
+            let _ = format!("Key (resource_id, timespan)=({}", s).parse::<ReservationConflictInfo>();
+        }
+
+        #[test]
+        fn conflict_should_parse_generated_keys(
+            new_rid in "\\PC{1,40}",
+            old_rid in "\\PC{1,40}",
+            bounds in prop::collection::vec(timestamp(), 4),
+        ) {
+            let bounds: Vec<_> = bounds.into_iter().map(pg_bound).collect();
+            let s = format!(
+                "Key {} conflicts with existing key {}.",
+                pg_key(&new_rid, &bounds[0], &bounds[1]),
+                pg_key(&old_rid, &bounds[2], &bounds[3]),
+            );
+
+            let conflict: ReservationConflict = s.parse().unwrap();
+            prop_assert_eq!(conflict.new.rid, new_rid);
+            prop_assert_eq!(conflict.old.rid, old_rid);
+            prop_assert_eq!(conflict.new.start, bounds[0].1.unwrap_or(DateTime::<Utc>::MIN_UTC));
+            prop_assert_eq!(conflict.new.end, bounds[1].1.unwrap_or(DateTime::<Utc>::MAX_UTC));
+            prop_assert_eq!(conflict.old.start, bounds[2].1.unwrap_or(DateTime::<Utc>::MIN_UTC));
+            prop_assert_eq!(conflict.old.end, bounds[3].1.unwrap_or(DateTime::<Utc>::MAX_UTC));
+        }
+    }
 }
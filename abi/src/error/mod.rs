@@ -1,5 +1,9 @@
 mod conflict;
 
+use crate::{
+    PolicyViolationDetail, PolicyViolationReason, ReservationConflictDetail, ReservationStatus,
+};
+use prost::Message;
 use sqlx::postgres::PgDatabaseError;
 // use std::io;
 
@@ -34,6 +38,12 @@ pub enum Error {
     #[error("Conflict reservation")]
     ConflictReservation(ReservationConflictInfo),
 
+    #[error("Conflict reservation at index {0} of the batch")]
+    BatchConflict(usize, ReservationConflictInfo),
+
+    #[error("Empty reservation batch")]
+    EmptyBatch,
+
     #[error("Invalid reservation id: `{0}`")]
     InvalidReservationId(i64),
 
@@ -52,6 +62,63 @@ pub enum Error {
     #[error("Invalid status: `{0}`")]
     InvalidStatus(i32),
 
+    #[error("Invalid consumer id: `{0}`")]
+    InvalidConsumerId(String),
+
+    #[error("Invalid series id: `{0}`")]
+    InvalidSeriesId(i64),
+
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrence(String),
+
+    #[error("Invalid hold ttl: {0}")]
+    InvalidHoldTtl(i64),
+
+    #[error("Hold expires in the past: `{0}`")]
+    InvalidHoldExpiry(i64),
+
+    #[error("Invalid duration: `{0}`")]
+    InvalidDuration(i64),
+
+    #[error("Unknown or inactive resource: `{0}`")]
+    UnknownResource(String),
+
+    #[error("Resource already exists: `{0}`")]
+    DuplicateResource(String),
+
+    #[error("Invalid resource capacity: `{0}`")]
+    InvalidCapacity(i32),
+
+    #[error("Invalid resource attributes: {0}")]
+    InvalidResourceAttributes(String),
+
+    #[error("Invalid parent resource: `{0}`")]
+    InvalidResourceParent(String),
+
+    #[error("Invalid resource group: {0}")]
+    InvalidResourceGroup(String),
+
+    #[error("Resource group already exists: `{0}`")]
+    DuplicateResourceGroup(String),
+
+    #[error("No resource of the group is available: `{0}`")]
+    NoAvailableResource(String),
+
+    #[error("Window is free on resource `{0}`, reserve it instead of waiting")]
+    WindowNotTaken(String),
+
+    #[error("Invalid booking rules: {0}")]
+    InvalidBookingRules(String),
+
+    #[error("Reservation violates the booking rules of the resource: {}", .0.as_str_name())]
+    PolicyViolation(PolicyViolationReason),
+
+    #[error("Cannot change reservation status from {0:?} to {1:?}")]
+    InvalidStatusTransition(ReservationStatus, ReservationStatus),
+
+    #[error("Admin token is missing or invalid")]
+    PermissionDenied,
+
     #[error("unknown error")]
     Unknown,
 }
@@ -64,9 +131,37 @@ impl PartialEq for Error {
             (Error::NotFound, Error::NotFound) => true,
             (Error::InvalidTime, Error::InvalidTime) => true,
             (Error::ConflictReservation(v1), Error::ConflictReservation(v2)) => v1 == v2,
+            (Error::BatchConflict(i1, v1), Error::BatchConflict(i2, v2)) => i1 == i2 && v1 == v2,
+            (Error::EmptyBatch, Error::EmptyBatch) => true,
             (Error::InvalidReservationId(v1), Error::InvalidReservationId(v2)) => v1 == v2,
             (Error::InvalidUserId(v1), Error::InvalidUserId(v2)) => v1 == v2,
             (Error::InvalidResourceId(v1), Error::InvalidResourceId(v2)) => v1 == v2,
+            (Error::InvalidCursor(v1), Error::InvalidCursor(v2)) => v1 == v2,
+            (Error::InvalidStatus(v1), Error::InvalidStatus(v2)) => v1 == v2,
+            (Error::InvalidConsumerId(v1), Error::InvalidConsumerId(v2)) => v1 == v2,
+            (Error::InvalidSeriesId(v1), Error::InvalidSeriesId(v2)) => v1 == v2,
+            (Error::InvalidRecurrence(v1), Error::InvalidRecurrence(v2)) => v1 == v2,
+            (Error::InvalidHoldTtl(v1), Error::InvalidHoldTtl(v2)) => v1 == v2,
+            (Error::InvalidHoldExpiry(v1), Error::InvalidHoldExpiry(v2)) => v1 == v2,
+            (Error::InvalidDuration(v1), Error::InvalidDuration(v2)) => v1 == v2,
+            (Error::UnknownResource(v1), Error::UnknownResource(v2)) => v1 == v2,
+            (Error::DuplicateResource(v1), Error::DuplicateResource(v2)) => v1 == v2,
+            (Error::InvalidCapacity(v1), Error::InvalidCapacity(v2)) => v1 == v2,
+            (Error::InvalidResourceAttributes(v1), Error::InvalidResourceAttributes(v2)) => {
+                v1 == v2
+            }
+            (Error::InvalidResourceParent(v1), Error::InvalidResourceParent(v2)) => v1 == v2,
+            (Error::InvalidResourceGroup(v1), Error::InvalidResourceGroup(v2)) => v1 == v2,
+            (Error::DuplicateResourceGroup(v1), Error::DuplicateResourceGroup(v2)) => v1 == v2,
+            (Error::NoAvailableResource(v1), Error::NoAvailableResource(v2)) => v1 == v2,
+            (Error::WindowNotTaken(v1), Error::WindowNotTaken(v2)) => v1 == v2,
+            (Error::InvalidBookingRules(v1), Error::InvalidBookingRules(v2)) => v1 == v2,
+            (Error::PolicyViolation(v1), Error::PolicyViolation(v2)) => v1 == v2,
+            (
+                Error::InvalidStatusTransition(from1, to1),
+                Error::InvalidStatusTransition(from2, to2),
+            ) => from1 == from2 && to1 == to2,
+            (Error::PermissionDenied, Error::PermissionDenied) => true,
             (Error::Unknown, Error::Unknown) => true,
             _ => false,
         }
@@ -77,11 +172,32 @@ impl From<sqlx::Error> for Error {
     fn from(e: sqlx::Error) -> Self {
         match e {
             sqlx::Error::Database(e) => {
-                let err: &PgDatabaseError = e.downcast_ref();
+                let Some(err) = e.try_downcast_ref::<PgDatabaseError>() else {
+                    return Error::DbError(sqlx::Error::Database(e));
+                };
                 match (err.code(), err.schema(), err.table()) {
                     ("23P01", Some("rsvp"), Some("reservations")) => {
-                        Error::ConflictReservation(err.detail().unwrap().parse().unwrap())
                         // ConflictReservation获取的是String，把String Parse成一个ReservationConflictInfo
+                        // without a detail the message is kept as unparsed info
+                        let Ok(info) = err.detail().unwrap_or_else(|| err.message()).parse();
+                        Error::ConflictReservation(info)
+                    }
+                    ("23503", Some("rsvp"), Some("reservations")) => {
+                        let detail = err.detail().unwrap_or_default();
+                        Error::UnknownResource(missing_key(detail, "resource_id").into())
+                    }
+                    ("23503", Some("rsvp"), Some("resource_group_members" | "waitlist")) => {
+                        let detail = err.detail().unwrap_or_default();
+                        Error::UnknownResource(missing_key(detail, "resource_id").into())
+                    }
+                    ("23503", Some("rsvp"), Some("resources")) => {
+                        let detail = err.detail().unwrap_or_default();
+                        Error::InvalidResourceParent(missing_key(detail, "parent_id").into())
+                    }
+                    // raised by rsvp.resources_hierarchy() for a parent which is also a descendant, or
+                    // whose hierarchy has reservations conflicting with the ones of the resource
+                    ("23514", Some("rsvp"), Some("resources")) => {
+                        Error::InvalidResourceParent(err.detail().unwrap_or_default().into())
                     }
                     _ => Error::DbError(sqlx::Error::Database(e)),
                 }
@@ -92,6 +208,14 @@ impl From<sqlx::Error> for Error {
     }
 }
 
+// "Key (resource_id)=(room-1) is not present in table \"resources\".", the detail if it doesn't match
+fn missing_key<'a>(detail: &'a str, column: &str) -> &'a str {
+    detail
+        .strip_prefix(&format!("Key ({})=(", column))
+        .and_then(|s| s.rsplit_once(") is not present"))
+        .map_or(detail, |(key, _)| key)
+}
+
 impl From<Error> for tonic::Status {
     fn from(e: Error) -> Self {
         match e {
@@ -104,14 +228,69 @@ impl From<Error> for tonic::Status {
             | Error::InvalidResourceId(_)
             | Error::InvalidPageSize(_)
             | Error::InvalidCursor(_)
-            | Error::InvalidStatus(_) => tonic::Status::invalid_argument(e.to_string()),
-            Error::ConflictReservation(info) => {
-                tonic::Status::failed_precondition(format!("Conflict reservation: {:?}", info))
+            | Error::InvalidStatus(_)
+            | Error::InvalidConsumerId(_)
+            | Error::InvalidSeriesId(_)
+            | Error::InvalidRecurrence(_)
+            | Error::InvalidHoldTtl(_)
+            | Error::InvalidHoldExpiry(_)
+            | Error::InvalidDuration(_)
+            | Error::InvalidCapacity(_)
+            | Error::InvalidResourceAttributes(_)
+            | Error::InvalidResourceParent(_)
+            | Error::InvalidResourceGroup(_)
+            | Error::InvalidBookingRules(_)
+            | Error::EmptyBatch => tonic::Status::invalid_argument(e.to_string()),
+            Error::ConflictReservation(ref info) => ReservationConflictDetail::from(info)
+                .attach_to(tonic::Status::failed_precondition(e.to_string())),
+            Error::BatchConflict(index, ref info) => ReservationConflictDetail {
+                batch_index: Some(index as i32),
+                ..info.into()
+            }
+            .attach_to(tonic::Status::failed_precondition(e.to_string())),
+            Error::InvalidStatusTransition(_, _)
+            | Error::UnknownResource(_)
+            | Error::NoAvailableResource(_)
+            | Error::WindowNotTaken(_) => tonic::Status::failed_precondition(e.to_string()),
+            Error::PolicyViolation(reason) => PolicyViolationDetail {
+                reason: reason as i32,
+            }
+            .attach_to(tonic::Status::failed_precondition(e.to_string())),
+            Error::DuplicateResource(_) | Error::DuplicateResourceGroup(_) => {
+                tonic::Status::already_exists(e.to_string())
             }
             Error::NotFound => {
                 tonic::Status::not_found("No reservation found by the given condition")
             }
+            Error::PermissionDenied => tonic::Status::permission_denied(e.to_string()),
             Error::Unknown => tonic::Status::unknown("unknown error"),
         }
     }
 }
+
+/// detail message attached as binary details to the FAILED_PRECONDITION status of an error
+pub trait StatusDetail: Message + Default {
+    /// client side helper: decode the detail from the status returned by the server, if any
+    fn from_status(status: &tonic::Status) -> Option<Self> {
+        if status.code() != tonic::Code::FailedPrecondition || status.details().is_empty() {
+            return None;
+        }
+        Self::decode(status.details()).ok().filter(Self::is_valid)
+    }
+
+    /// attach the detail to the status as binary details, replacing the existing ones
+    fn attach_to(&self, status: tonic::Status) -> tonic::Status {
+        tonic::Status::with_details(status.code(), status.message(), self.encode_to_vec().into())
+    }
+
+    /// whether a decoded detail is one of this kind, details of another kind may decode too
+    fn is_valid(&self) -> bool {
+        true
+    }
+}
+
+impl StatusDetail for PolicyViolationDetail {
+    fn is_valid(&self) -> bool {
+        self.reason() != PolicyViolationReason::Unknown
+    }
+}
@@ -6,8 +6,12 @@ mod types;
 mod utils;
 
 pub use config::*;
-pub use error::{Error, ReservationConflict, ReservationConflictInfo, ResrvationWindow};
+pub use error::{
+    Error, ReservationConflict, ReservationConflictInfo, ResrvationWindow, StatusDetail,
+};
 pub use pb::*;
+use sqlx::{Postgres, QueryBuilder};
+pub use types::{get_timespan, nearest_windows, validate_range, MAX_OCCURRENCES};
 pub use utils::*;
 // use sqlx::error::DatabaseError;
 
@@ -34,13 +38,25 @@ pub trait Normalizer: Validator {
 }
 
 /// datdbase equivalent of the "reservation_status" enum
-#[derive(Debug, Clone, PartialEq, Eq, sqlx::Type)]
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::Type, serde::Deserialize)]
 #[sqlx(type_name = "reservation_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum RsvpStatus {
     Unknown,
     Pending,
     Confirmed,
     Blocked,
+    Cancelled,
+}
+
+/// database equivalent of the "reservation_update_type" enum
+#[derive(Debug, Clone, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "reservation_update_type", rename_all = "lowercase")]
+pub enum RsvpUpdateType {
+    Unknown,
+    Create,
+    Update,
+    Delete,
 }
 
 impl Validator for ReservationId {
@@ -53,6 +69,7 @@ impl Validator for ReservationId {
     }
 }
 
+/// build the sql for the data structure, user provided values are passed as bind parameters
 pub trait ToSql {
-    fn to_sql(&self) -> String;
+    fn to_sql(&self) -> QueryBuilder<'static, Postgres>;
 }
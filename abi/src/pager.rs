@@ -21,6 +21,8 @@ pub struct PageInfo {
 pub trait Paginator: Sized {
     fn get_pager<T: Id>(&self, data: &mut VecDeque<T>) -> Pager;
     fn next_page(&self, pager: &Pager) -> Option<Self>;
+    // filter only pages forward for now
+    #[allow(dead_code)]
     fn prev_page(&self, pager: &Pager) -> Option<Self>;
 }
 
@@ -91,7 +93,7 @@ pub mod pager_test_utils {
     }
 
     pub fn generate_test_ids(start: i64, end: i64) -> VecDeque<TestId> {
-        (start..=end).map(|i| TestId(i)).collect()
+        (start..=end).map(TestId).collect()
     }
 }
 
@@ -24,13 +24,38 @@ pub struct Reservation {
     /// extra note
     #[prost(string, tag = "7")]
     pub note: ::prost::alloc::string::String,
+    /// series the reservation belongs to if it is an occurrence of a recurring reservation, otherwise 0
+    #[prost(int64, tag = "8")]
+    pub series_id: i64,
+    /// a pending reservation not confirmed by then is cancelled by the server, it must be in the future. empty means it is held
+    /// until confirmed or cancelled
+    #[prost(message, optional, tag = "9")]
+    pub hold_expires_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// pending reservation overlapping a blackout, it doesn't hold its window until rescheduled or transferred
+    #[prost(bool, tag = "10")]
+    pub flagged: bool,
+    /// window blocked on the resource: start and end padded with the buffers of the resource. set by the server
+    #[prost(message, optional, tag = "11")]
+    pub blocked_start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "12")]
+    pub blocked_end: ::core::option::Option<::prost_types::Timestamp>,
 }
-/// To make a reservation, send a ReservationRequest with Reservation object(id should be empty)
+/// To make a reservation, send a ReservationRequest with Reservation object(id should be empty, status
+/// pending or unset, a reservation always starts pending)
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReserveRequest {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// seconds to hold a pending reservation before it expires, it overrides hold_expires_at of the reservation. 0 means no override
+    #[prost(int64, tag = "2")]
+    pub hold_ttl: i64,
+    /// on conflict, suggest up to this many nearest free windows of the same duration in ReservationConflictDetail
+    #[prost(int32, tag = "3")]
+    pub suggestions: i32,
+    /// also suggest free windows on these resources
+    #[prost(string, repeated, tag = "4")]
+    pub sibling_resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 /// Created reservation will be returned in ReserveResponse
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -39,6 +64,311 @@ pub struct ReserveResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To make several reservations at once, send a ReserveBatchRequest. Either all of them are made or none.
+/// An empty batch is rejected with INVALID_ARGUMENT
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBatchRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// Created reservations will be returned in ReserveBatchResponse, in the same order as requested
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveBatchResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// RRULE-like recurrence rule, occurrences repeat the start/end of the first one. the rule is
+/// expanded in UTC only: days, weeks and months are added to the UTC time, so an occurrence keeps
+/// its UTC time of day and moves by an hour in local time across a DST change
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecurrenceRule {
+    #[prost(enumeration = "RecurrenceFrequency", tag = "1")]
+    pub frequency: i32,
+    /// repeat every interval days/weeks/months. if 0, use 1
+    #[prost(int32, tag = "2")]
+    pub interval: i32,
+    /// number of occurrences including the first one, exceptions are counted as well. 0 means unlimited
+    #[prost(int32, tag = "3")]
+    pub count: i32,
+    /// no occurrence starts after until. count or until must be set
+    #[prost(message, optional, tag = "4")]
+    pub until: ::core::option::Option<::prost_types::Timestamp>,
+    /// start time of the occurrences to skip, a rule skipping every occurrence is rejected
+    #[prost(message, repeated, tag = "5")]
+    pub exceptions: ::prost::alloc::vec::Vec<::prost_types::Timestamp>,
+}
+/// To make a recurring reservation, send a ReserveRecurringRequest with the first occurrence and a rule
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveRecurringRequest {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+    #[prost(message, optional, tag = "2")]
+    pub rule: ::core::option::Option<RecurrenceRule>,
+}
+/// Occurrences made or skipped because of conflicts will be returned in ReserveRecurringResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveRecurringResponse {
+    /// series id shared by all the occurrences
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+    /// created occurrences
+    #[prost(message, repeated, tag = "2")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+    /// occurrences conflicting with existing reservations, they are not made
+    #[prost(message, repeated, tag = "3")]
+    pub conflicts: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To reserve any free resource of a group, send a ReserveAnyRequest. resource_id of the reservation is ignored
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyRequest {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+    #[prost(string, tag = "2")]
+    pub group_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "SelectionStrategy", tag = "3")]
+    pub strategy: i32,
+}
+/// The reservation made on the chosen resource will be returned in ReserveAnyResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReserveAnyResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// request queued for a window taken by other reservations. when an overlapping reservation is cancelled
+/// the entries are promoted to pending reservations in the order they joined, and removed from the waitlist
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WaitlistEntry {
+    /// unique id for an entry, if put into JoinWaitlistRequest, id should be empty
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "5")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// note of the reservation made on promotion
+    #[prost(string, tag = "6")]
+    pub note: ::prost::alloc::string::String,
+}
+/// To queue for a taken window, send a JoinWaitlistRequest. joining twice for the same window keeps the place in the queue
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinWaitlistRequest {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct JoinWaitlistResponse {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaveWaitlistResponse {
+    #[prost(message, optional, tag = "1")]
+    pub entry: ::core::option::Option<WaitlistEntry>,
+}
+/// entries of a resource overlapping the window, in the order they are served. empty start or end is unbounded
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWaitlistRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListWaitlistResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<WaitlistEntry>,
+}
+/// To cancel all the occurrences of a recurring reservation, send a CancelSeriesRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesRequest {
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+}
+/// Cancelled occurrences will be returned in CancelSeriesResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To update all the occurrences of a recurring reservation, send an UpdateSeriesRequest. Either every occurrence
+/// is updated or none, it fails with FAILED_PRECONDITION if a moved occurrence conflicts
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesRequest {
+    #[prost(int64, tag = "1")]
+    pub series_id: i64,
+    /// new note of every occurrence, kept if unset
+    #[prost(string, optional, tag = "2")]
+    pub note: ::core::option::Option<::prost::alloc::string::String>,
+    /// seconds to move the occurrences which are not cancelled by, may be negative
+    #[prost(int64, tag = "3")]
+    pub shift: i64,
+}
+/// Updated occurrences will be returned in UpdateSeriesResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateSeriesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub reservations: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To move a reservation to another status, send a TransitionRequest
+/// allowed transitions: pending -> confirmed, pending/confirmed -> blocked, blocked -> pending
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    #[prost(enumeration = "ReservationStatus", tag = "2")]
+    pub status: i32,
+}
+/// Reservation in its new status will be returned in TransitionResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransitionResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To take a resource offline for a window, send a BlackoutRequest. the blackout is a blocked reservation without user id
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlackoutRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// reason of the blackout
+    #[prost(string, tag = "4")]
+    pub note: ::prost::alloc::string::String,
+    #[prost(enumeration = "BlackoutPolicy", tag = "5")]
+    pub policy: i32,
+}
+/// Created blackout and the pending reservations cancelled or flagged by its policy will be returned in BlackoutResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlackoutResponse {
+    #[prost(message, optional, tag = "1")]
+    pub blackout: ::core::option::Option<Reservation>,
+    #[prost(message, repeated, tag = "2")]
+    pub affected: ::prost::alloc::vec::Vec<Reservation>,
+}
+/// To find the free slots of resources in a window, send an AvailabilityRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityRequest {
+    /// resources to search, at least one. unknown or inactive resources have no free slot
+    #[prost(string, repeated, tag = "1")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+    /// only return slots lasting at least min_duration seconds. if 0, return every slot
+    #[prost(int64, tag = "4")]
+    pub min_duration: i64,
+}
+/// a window in which a resource is not reserved
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FreeSlot {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Free slots ordered by resource id and start time will be returned in AvailabilityResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AvailabilityResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub slots: ::prost::alloc::vec::Vec<FreeSlot>,
+}
+/// a window of a resource in a reservation conflict
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationWindow {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// an existing reservation holding (part of) the window asked for
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ConflictingReservation {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// owner of the reservation, empty if the caller is not allowed to see it or for blackouts
+    #[prost(string, tag = "2")]
+    pub user_id: ::prost::alloc::string::String,
+    #[prost(enumeration = "ReservationStatus", tag = "3")]
+    pub status: i32,
+    /// resource of the reservation, an ancestor or a descendant of the resource asked for if not the same
+    #[prost(string, tag = "4")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+/// Attached as binary details to the FAILED_PRECONDITION status of a conflicting reservation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReservationConflictDetail {
+    /// window asked for, padded with the buffers of the resource
+    #[prost(message, optional, tag = "1")]
+    pub new: ::core::option::Option<ReservationWindow>,
+    /// blocked window of the existing reservation it conflicts with, its resource_id is the conflicting resource
+    #[prost(message, optional, tag = "2")]
+    pub old: ::core::option::Option<ReservationWindow>,
+    /// id of the existing reservation, 0 if unknown
+    #[prost(int64, tag = "3")]
+    pub conflicting_id: i64,
+    /// nearest free windows of the same duration, nearest first, if asked for in ReserveRequest
+    #[prost(message, repeated, tag = "4")]
+    pub suggestions: ::prost::alloc::vec::Vec<FreeSlot>,
+    /// database message if the windows could not be parsed from it, new and old are empty then
+    #[prost(string, tag = "5")]
+    pub unparsed: ::prost::alloc::string::String,
+    /// every existing reservation overlapping the new window, conflicting_id is the first of them
+    #[prost(message, repeated, tag = "6")]
+    pub conflicting: ::prost::alloc::vec::Vec<ConflictingReservation>,
+    /// sub-windows of the new window in which the resource is fully booked, or blocked by a reservation of an
+    /// ancestor or a descendant
+    #[prost(message, repeated, tag = "7")]
+    pub saturated: ::prost::alloc::vec::Vec<ReservationWindow>,
+    /// position of the conflicting reservation in ReserveBatchRequest, unset outside of a batch
+    #[prost(int32, optional, tag = "8")]
+    pub batch_index: ::core::option::Option<i32>,
+}
 /// To update a reservation, Send an UpdateRequest. Only note is updatable
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -55,6 +385,43 @@ pub struct UpdateResponse {
     #[prost(message, optional, tag = "1")]
     pub reservation: ::core::option::Option<Reservation>,
 }
+/// To move a reservation to another time window, send a RescheduleRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// new start time for the reservation
+    #[prost(message, optional, tag = "2")]
+    pub start: ::core::option::Option<::prost_types::Timestamp>,
+    /// new end time for the reservation
+    #[prost(message, optional, tag = "3")]
+    pub end: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// Rescheduled reservation will be returned in RescheduleResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RescheduleResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
+/// To move a reservation to another resource, send a TransferRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferRequest {
+    #[prost(int64, tag = "1")]
+    pub id: i64,
+    /// resource the reservation is moved to
+    #[prost(string, tag = "2")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+/// Transferred reservation will be returned in TransferResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferResponse {
+    #[prost(message, optional, tag = "1")]
+    pub reservation: ::core::option::Option<Reservation>,
+}
 /// To change a reservation status from PENDING to CONFIRMED, send a ConfirmRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -139,6 +506,10 @@ pub struct ReservationQuery {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// also return blackouts overlapping the window, whatever the user id and status are
+    #[prost(bool, tag = "7")]
+    #[builder(setter(into), default)]
+    pub include_blackouts: bool,
 }
 /// To query reservations order by reservation id
 #[derive(derive_builder::Builder)]
@@ -170,6 +541,10 @@ pub struct ReservationFilter {
     #[prost(bool, tag = "6")]
     #[builder(setter(into), default)]
     pub desc: bool,
+    /// also return blackouts, whatever the user id and status are
+    #[prost(bool, tag = "7")]
+    #[builder(setter(into), default)]
+    pub include_blackouts: bool,
 }
 /// to query reservations, send a QueryRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -201,7 +576,16 @@ pub struct FilterResponse {
 /// Client can listen to reservation changes by sending a ListenRequest
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ListenRequest {}
+pub struct ListenRequest {
+    /// replay changes starting from this change id. changes are streamed in commit safe order, not in id order, so a
+    /// few changes streamed after it with a lower id may be replayed as well. if not set, resume from the acknowledged
+    /// position of consumer_id, or only stream changes made after the request
+    #[prost(int64, optional, tag = "1")]
+    pub start_id: ::core::option::Option<i64>,
+    /// named consumer, server keeps its acknowledged position (see AckRequest)
+    #[prost(string, tag = "2")]
+    pub consumer_id: ::prost::alloc::string::String,
+}
 /// Server will send reservation changes to client in streaming response
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -212,60 +596,451 @@ pub struct ListenResponse {
     /// reservation id for updated reservation
     #[prost(message, optional, tag = "2")]
     pub reservation: ::core::option::Option<Reservation>,
+    /// change id, use it as start_id to resume, or acknowledge it with AckRequest
+    #[prost(int64, tag = "3")]
+    pub id: i64,
+    /// reservation before the update, only populated when op is UPDATE
+    #[prost(message, optional, tag = "4")]
+    pub old: ::core::option::Option<Reservation>,
 }
-/// reervation status for a given time period
-#[derive(
-    sqlx::Type, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration,
-)]
-#[repr(i32)]
-pub enum ReservationStatus {
-    Unknown = 0,
-    Pending = 1,
-    Confirmed = 2,
-    Blocked = 3,
+/// To acknowledge that a consumer has processed all changes up to change_id, send an AckRequest
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckRequest {
+    #[prost(string, tag = "1")]
+    pub consumer_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub change_id: i64,
 }
-impl ReservationStatus {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
-            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
-            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
-            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
-        }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "RESERVATION_STATUS_UNKNOWN" => Some(Self::Unknown),
-            "RESERVATION_STATUS_PENDING" => Some(Self::Pending),
-            "RESERVATION_STATUS_CONFIRMED" => Some(Self::Confirmed),
-            "RESERVATION_STATUS_BLOCKED" => Some(Self::Blocked),
-            _ => None,
-        }
-    }
+/// Acknowledged position of the consumer will be returned in AckResponse
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AckResponse {
+    #[prost(string, tag = "1")]
+    pub consumer_id: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub change_id: i64,
 }
-///   when reservation is updated, record the update type
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ReservationUpdateType {
-    Unknown = 0,
-    Create = 1,
-    Update = 2,
-    Delete = 3,
+/// An entry of the resource catalog, reservations can only be made on known resources
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Resource {
+    /// unique id of the resource, referenced by reservations
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// free form kind of the resource, e.g. room, device
+    #[prost(string, tag = "3")]
+    pub r#type: ::prost::alloc::string::String,
+    /// JSON object of arbitrary attributes, empty means no attribute
+    #[prost(string, tag = "4")]
+    pub attributes: ::prost::alloc::string::String,
+    /// inactive resources can't be reserved anymore, their reservations are kept. unset means active when the
+    /// resource is created, and unchanged when it is updated
+    #[prost(bool, optional, tag = "5")]
+    pub active: ::core::option::Option<bool>,
+    /// number of reservations which may overlap, e.g. desks of a pool. 0 is the same as 1
+    #[prost(int32, tag = "6")]
+    pub capacity: i32,
+    /// seconds the resource is blocked before and after every reservation, e.g. to clean a room. a change
+    /// only applies to reservations made or moved afterwards
+    #[prost(int64, tag = "7")]
+    pub buffer_before: i64,
+    #[prost(int64, tag = "8")]
+    pub buffer_after: i64,
+    /// resource this one is a part of, e.g. the lab of a bench. empty for a top level resource. a reservation
+    /// conflicts with the reservations of the ancestors and the descendants of its resource, so a new parent is
+    /// rejected if the reservations of the new ancestors conflict with the ones of this resource or its descendants
+    #[prost(string, tag = "9")]
+    pub parent_id: ::prost::alloc::string::String,
 }
-impl ReservationUpdateType {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
-            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
+/// weekly window in which a resource can be booked, in the time zone of its booking rules
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpeningHours {
+    /// ISO weekday, 1 is Monday and 7 is Sunday
+    #[prost(int32, tag = "1")]
+    pub weekday: i32,
+    /// minutes since midnight, closes may be 1440 for midnight of the next day
+    #[prost(int32, tag = "2")]
+    pub opens: i32,
+    #[prost(int32, tag = "3")]
+    pub closes: i32,
+}
+/// Rules checked when a reservation is made on the resource, zero values mean no limit
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BookingRules {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+    /// IANA time zone of the opening hours, e.g. Europe/Paris. UTC if empty
+    #[prost(string, tag = "2")]
+    pub timezone: ::prost::alloc::string::String,
+    /// a reservation must fit in one of the windows, any time is fine if empty
+    #[prost(message, repeated, tag = "3")]
+    pub opening_hours: ::prost::alloc::vec::Vec<OpeningHours>,
+    /// in seconds
+    #[prost(int64, tag = "4")]
+    pub min_duration: i64,
+    #[prost(int64, tag = "5")]
+    pub max_duration: i64,
+    /// how far ahead of now a reservation may start, in seconds
+    #[prost(int64, tag = "6")]
+    pub max_lead_time: i64,
+}
+/// Attached as binary details to the FAILED_PRECONDITION status of a reservation violating the rules
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PolicyViolationDetail {
+    #[prost(enumeration = "PolicyViolationReason", tag = "1")]
+    pub reason: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// name, type, attributes, active, capacity, buffers and parent of the resource with the given id are replaced.
+/// lowering the capacity below the reservations already overlapping fails with FAILED_PRECONDITION
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceRequest {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// list resources ordered by id
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesRequest {
+    /// only list resources of the type, empty for all types
+    #[prost(string, tag = "1")]
+    pub r#type: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub include_inactive: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourcesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub resources: ::prost::alloc::vec::Vec<Resource>,
+}
+/// replace the booking rules of a resource
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBookingRulesRequest {
+    #[prost(message, optional, tag = "1")]
+    pub rules: ::core::option::Option<BookingRules>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetBookingRulesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub rules: ::core::option::Option<BookingRules>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBookingRulesRequest {
+    #[prost(string, tag = "1")]
+    pub resource_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetBookingRulesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub rules: ::core::option::Option<BookingRules>,
+}
+/// named set of interchangeable resources, e.g. identical devices
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResourceGroup {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    /// members in order, first fit tries them in this order
+    #[prost(string, repeated, tag = "3")]
+    pub resource_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceGroupRequest {
+    #[prost(message, optional, tag = "1")]
+    pub group: ::core::option::Option<ResourceGroup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateResourceGroupResponse {
+    #[prost(message, optional, tag = "1")]
+    pub group: ::core::option::Option<ResourceGroup>,
+}
+/// name and members of the group with the given id are replaced
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceGroupRequest {
+    #[prost(message, optional, tag = "1")]
+    pub group: ::core::option::Option<ResourceGroup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateResourceGroupResponse {
+    #[prost(message, optional, tag = "1")]
+    pub group: ::core::option::Option<ResourceGroup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceGroupRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetResourceGroupResponse {
+    #[prost(message, optional, tag = "1")]
+    pub group: ::core::option::Option<ResourceGroup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourceGroupsRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListResourceGroupsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub groups: ::prost::alloc::vec::Vec<ResourceGroup>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivateResourceRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DeactivateResourceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub resource: ::core::option::Option<Resource>,
+}
+/// reervation status for a given time period
+#[derive(
+    sqlx::Type, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum ReservationStatus {
+    Unknown = 0,
+    Pending = 1,
+    Confirmed = 2,
+    Blocked = 3,
+    Cancelled = 4,
+}
+impl ReservationStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationStatus::Unknown => "RESERVATION_STATUS_UNKNOWN",
+            ReservationStatus::Pending => "RESERVATION_STATUS_PENDING",
+            ReservationStatus::Confirmed => "RESERVATION_STATUS_CONFIRMED",
+            ReservationStatus::Blocked => "RESERVATION_STATUS_BLOCKED",
+            ReservationStatus::Cancelled => "RESERVATION_STATUS_CANCELLED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RESERVATION_STATUS_UNKNOWN" => Some(Self::Unknown),
+            "RESERVATION_STATUS_PENDING" => Some(Self::Pending),
+            "RESERVATION_STATUS_CONFIRMED" => Some(Self::Confirmed),
+            "RESERVATION_STATUS_BLOCKED" => Some(Self::Blocked),
+            "RESERVATION_STATUS_CANCELLED" => Some(Self::Cancelled),
+            _ => None,
+        }
+    }
+}
+/// how often a recurring reservation repeats
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RecurrenceFrequency {
+    Unknown = 0,
+    Daily = 1,
+    Weekly = 2,
+    Monthly = 3,
+}
+impl RecurrenceFrequency {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            RecurrenceFrequency::Unknown => "RECURRENCE_FREQUENCY_UNKNOWN",
+            RecurrenceFrequency::Daily => "RECURRENCE_FREQUENCY_DAILY",
+            RecurrenceFrequency::Weekly => "RECURRENCE_FREQUENCY_WEEKLY",
+            RecurrenceFrequency::Monthly => "RECURRENCE_FREQUENCY_MONTHLY",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RECURRENCE_FREQUENCY_UNKNOWN" => Some(Self::Unknown),
+            "RECURRENCE_FREQUENCY_DAILY" => Some(Self::Daily),
+            "RECURRENCE_FREQUENCY_WEEKLY" => Some(Self::Weekly),
+            "RECURRENCE_FREQUENCY_MONTHLY" => Some(Self::Monthly),
+            _ => None,
+        }
+    }
+}
+/// what to do with the pending reservations overlapping a new blackout
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum BlackoutPolicy {
+    /// fail if the blackout overlaps any reservation
+    Reject = 0,
+    /// cancel the overlapping pending reservations
+    CancelPending = 1,
+    /// flag the overlapping pending reservations, their owners should reschedule or cancel them
+    FlagPending = 2,
+}
+impl BlackoutPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            BlackoutPolicy::Reject => "BLACKOUT_POLICY_REJECT",
+            BlackoutPolicy::CancelPending => "BLACKOUT_POLICY_CANCEL_PENDING",
+            BlackoutPolicy::FlagPending => "BLACKOUT_POLICY_FLAG_PENDING",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BLACKOUT_POLICY_REJECT" => Some(Self::Reject),
+            "BLACKOUT_POLICY_CANCEL_PENDING" => Some(Self::CancelPending),
+            "BLACKOUT_POLICY_FLAG_PENDING" => Some(Self::FlagPending),
+            _ => None,
+        }
+    }
+}
+/// how reserve_any picks a resource of a group, the next ones are tried if it is taken
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SelectionStrategy {
+    /// the first free resource in the order of the group
+    FirstFit = 0,
+    /// the free resource with the fewest upcoming reservations
+    LeastUsed = 1,
+    /// the free resource following the one picked last time in the group
+    RoundRobin = 2,
+}
+impl SelectionStrategy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SelectionStrategy::FirstFit => "SELECTION_STRATEGY_FIRST_FIT",
+            SelectionStrategy::LeastUsed => "SELECTION_STRATEGY_LEAST_USED",
+            SelectionStrategy::RoundRobin => "SELECTION_STRATEGY_ROUND_ROBIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SELECTION_STRATEGY_FIRST_FIT" => Some(Self::FirstFit),
+            "SELECTION_STRATEGY_LEAST_USED" => Some(Self::LeastUsed),
+            "SELECTION_STRATEGY_ROUND_ROBIN" => Some(Self::RoundRobin),
+            _ => None,
+        }
+    }
+}
+/// which booking rule of the resource a reservation violates
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PolicyViolationReason {
+    Unknown = 0,
+    /// the reservation doesn't fit in the opening hours of the resource
+    OutsideOpeningHours = 1,
+    TooShort = 2,
+    TooLong = 3,
+    /// the reservation starts further ahead than the max lead time
+    TooFarAhead = 4,
+}
+impl PolicyViolationReason {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PolicyViolationReason::Unknown => "POLICY_VIOLATION_REASON_UNKNOWN",
+            PolicyViolationReason::OutsideOpeningHours => {
+                "POLICY_VIOLATION_REASON_OUTSIDE_OPENING_HOURS"
+            }
+            PolicyViolationReason::TooShort => "POLICY_VIOLATION_REASON_TOO_SHORT",
+            PolicyViolationReason::TooLong => "POLICY_VIOLATION_REASON_TOO_LONG",
+            PolicyViolationReason::TooFarAhead => "POLICY_VIOLATION_REASON_TOO_FAR_AHEAD",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "POLICY_VIOLATION_REASON_UNKNOWN" => Some(Self::Unknown),
+            "POLICY_VIOLATION_REASON_OUTSIDE_OPENING_HOURS" => Some(Self::OutsideOpeningHours),
+            "POLICY_VIOLATION_REASON_TOO_SHORT" => Some(Self::TooShort),
+            "POLICY_VIOLATION_REASON_TOO_LONG" => Some(Self::TooLong),
+            "POLICY_VIOLATION_REASON_TOO_FAR_AHEAD" => Some(Self::TooFarAhead),
+            _ => None,
+        }
+    }
+}
+///   when reservation is updated, record the update type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ReservationUpdateType {
+    Unknown = 0,
+    Create = 1,
+    Update = 2,
+    Delete = 3,
+}
+impl ReservationUpdateType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ReservationUpdateType::Unknown => "RESERVATION_UPDATE_TYPE_UNKNOWN",
+            ReservationUpdateType::Create => "RESERVATION_UPDATE_TYPE_CREATE",
             ReservationUpdateType::Update => "RESERVATION_UPDATE_TYPE_UPDATE",
             ReservationUpdateType::Delete => "RESERVATION_UPDATE_TYPE_DELETE",
         }
@@ -384,11 +1159,12 @@ pub mod reservation_service_client {
                 .insert(GrpcMethod::new("reservation.ReservationService", "reserve"));
             self.inner.unary(req, path, codec).await
         }
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        pub async fn confirm(
+        /// make several reservations in one transaction, if any of them fails, none is made
+        pub async fn reserve_batch(
             &mut self,
-            request: impl tonic::IntoRequest<super::ConfirmRequest>,
-        ) -> std::result::Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveBatchRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveBatchResponse>, tonic::Status>
+        {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -396,18 +1172,23 @@ pub mod reservation_service_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/confirm");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_batch",
+            );
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("reservation.ReservationService", "confirm"));
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "reserve_batch",
+            ));
             self.inner.unary(req, path, codec).await
         }
-        /// update the reservation note
-        pub async fn update(
+        /// make a recurring reservation, occurrences conflicting with existing reservations are skipped. it fails
+        /// with FAILED_PRECONDITION if every occurrence conflicts
+        pub async fn reserve_recurring(
             &mut self,
-            request: impl tonic::IntoRequest<super::UpdateRequest>,
-        ) -> std::result::Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveRecurringRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveRecurringResponse>, tonic::Status>
+        {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -415,18 +1196,23 @@ pub mod reservation_service_client {
                 )
             })?;
             let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/update");
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/reserve_recurring",
+            );
             let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("reservation.ReservationService", "update"));
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "reserve_recurring",
+            ));
             self.inner.unary(req, path, codec).await
         }
-        /// cancel a reservation
-        pub async fn cancel(
+        /// reserve the window on a free resource of a group, picked by the strategy. fails with FAILED_PRECONDITION if
+        /// every resource of the group is taken
+        pub async fn reserve_any(
             &mut self,
-            request: impl tonic::IntoRequest<super::CancelRequest>,
-        ) -> std::result::Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            request: impl tonic::IntoRequest<super::ReserveAnyRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status>
+        {
             self.inner.ready().await.map_err(|e| {
                 tonic::Status::new(
                     tonic::Code::Unknown,
@@ -435,12 +1221,298 @@ pub mod reservation_service_client {
             })?;
             let codec = tonic::codec::ProstCodec::default();
             let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel");
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reserve_any");
             let mut req = request.into_request();
-            req.extensions_mut()
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "reserve_any",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// queue for a taken window, the entry is promoted to a pending reservation once the window is freed, unless it
+        /// breaks the booking rules of the resource by then. fails with FAILED_PRECONDITION if the window is free
+        pub async fn join_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::JoinWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::JoinWaitlistResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/join_waitlist",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "join_waitlist",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// leave a waitlist, the removed entry is returned
+        pub async fn leave_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::LeaveWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/leave_waitlist",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "leave_waitlist",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// list the waiting entries of a resource
+        pub async fn list_waitlist(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListWaitlistResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/list_waitlist",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "list_waitlist",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// confirm a pending reservation, a confirmed reservation is returned unchanged. other status fails with FAILED_PRECONDITION
+        pub async fn confirm(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ConfirmRequest>,
+        ) -> std::result::Result<tonic::Response<super::ConfirmResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/confirm");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("reservation.ReservationService", "confirm"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// move a reservation to another status, illegal transitions fail with FAILED_PRECONDITION
+        pub async fn transition(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TransitionRequest>,
+        ) -> std::result::Result<tonic::Response<super::TransitionResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/transition");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "transition",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// update the reservation note
+        pub async fn update(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/update");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("reservation.ReservationService", "update"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// move a reservation to another time window, keeping its id. a cancelled reservation fails with NOT_FOUND
+        pub async fn reschedule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RescheduleRequest>,
+        ) -> std::result::Result<tonic::Response<super::RescheduleResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/reschedule");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "reschedule",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// move a reservation to another resource, keeping its id and time window. a cancelled reservation fails with NOT_FOUND
+        pub async fn transfer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TransferRequest>,
+        ) -> std::result::Result<tonic::Response<super::TransferResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/transfer");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "transfer",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// cancel a reservation, it is kept with the cancelled status for history
+        pub async fn cancel(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelRequest>,
+        ) -> std::result::Result<tonic::Response<super::CancelResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/cancel");
+            let mut req = request.into_request();
+            req.extensions_mut()
                 .insert(GrpcMethod::new("reservation.ReservationService", "cancel"));
             self.inner.unary(req, path, codec).await
         }
+        /// cancel all the occurrences of a recurring reservation, use cancel for a single occurrence
+        pub async fn cancel_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/cancel_series",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "cancel_series",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// update the note of all the occurrences of a recurring reservation, or move them all
+        pub async fn update_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/update_series",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "update_series",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// admin only, needs the `authorization: Bearer <admin_token>` metadata: take a resource offline for a window for
+        /// all users. fails with PERMISSION_DENIED for other callers, so do transition, reschedule, transfer and cancel of a
+        /// blackout
+        pub async fn blackout(
+            &mut self,
+            request: impl tonic::IntoRequest<super::BlackoutRequest>,
+        ) -> std::result::Result<tonic::Response<super::BlackoutResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/blackout");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "blackout",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// find the free slots of resources in a window
+        pub async fn availability(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AvailabilityRequest>,
+        ) -> std::result::Result<tonic::Response<super::AvailabilityResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ReservationService/availability",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ReservationService",
+                "availability",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
         /// get a reservation by id
         pub async fn get(
             &mut self,
@@ -500,88 +1572,1570 @@ pub mod reservation_service_client {
                 .insert(GrpcMethod::new("reservation.ReservationService", "filter"));
             self.inner.unary(req, path, codec).await
         }
-        /// another system could monitor newly added/confirmed/cancelled reservations
-        pub async fn listen(
-            &mut self,
-            request: impl tonic::IntoRequest<super::ListenRequest>,
-        ) -> std::result::Result<
-            tonic::Response<tonic::codec::Streaming<super::Reservation>>,
-            tonic::Status,
-        > {
-            self.inner.ready().await.map_err(|e| {
-                tonic::Status::new(
-                    tonic::Code::Unknown,
-                    format!("Service was not ready: {}", e.into()),
-                )
-            })?;
-            let codec = tonic::codec::ProstCodec::default();
-            let path =
-                http::uri::PathAndQuery::from_static("/reservation.ReservationService/listen");
-            let mut req = request.into_request();
-            req.extensions_mut()
-                .insert(GrpcMethod::new("reservation.ReservationService", "listen"));
-            self.inner.server_streaming(req, path, codec).await
+        /// another system could monitor newly added/confirmed/cancelled reservations
+        pub async fn listen(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListenRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::ListenResponse>>,
+            tonic::Status,
+        > {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ReservationService/listen");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("reservation.ReservationService", "listen"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// acknowledge processed changes, so a named consumer can resume after reconnecting
+        pub async fn ack(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AckRequest>,
+        ) -> std::result::Result<tonic::Response<super::AckResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/reservation.ReservationService/ack");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("reservation.ReservationService", "ack"));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated client implementations.
+pub mod resource_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct ResourceServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl ResourceServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> ResourceServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ResourceServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<http::Request<tonic::body::BoxBody>>>::Error:
+                Into<StdError> + Send + Sync,
+        {
+            ResourceServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// add a resource to the catalog
+        pub async fn create_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateResourceResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/create_resource",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "create_resource",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// update a resource, the id can't be changed
+        pub async fn update_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/update_resource",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "update_resource",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// get a resource by id
+        pub async fn get_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetResourceResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ResourceService/get_resource");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "get_resource",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// list the resources of the catalog
+        pub async fn list_resources(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListResourcesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListResourcesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/reservation.ResourceService/list_resources");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "list_resources",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// stop accepting reservations on a resource, existing reservations are kept
+        pub async fn deactivate_resource(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeactivateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeactivateResourceResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/deactivate_resource",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "deactivate_resource",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// replace the rules checked when reserving a resource, e.g. opening hours
+        pub async fn set_booking_rules(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SetBookingRulesRequest>,
+        ) -> std::result::Result<tonic::Response<super::SetBookingRulesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/set_booking_rules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "set_booking_rules",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// get the booking rules of a resource, a resource without rules has empty ones
+        pub async fn get_booking_rules(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetBookingRulesRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetBookingRulesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/get_booking_rules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "get_booking_rules",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// add a group of resources
+        pub async fn create_resource_group(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateResourceGroupResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/create_resource_group",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "create_resource_group",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// replace the name and the members of a group
+        pub async fn update_resource_group(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResourceGroupResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/update_resource_group",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "update_resource_group",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// get a group by id
+        pub async fn get_resource_group(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetResourceGroupResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/get_resource_group",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "get_resource_group",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        /// list the groups ordered by id
+        pub async fn list_resource_groups(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListResourceGroupsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListResourceGroupsResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::new(
+                    tonic::Code::Unknown,
+                    format!("Service was not ready: {}", e.into()),
+                )
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/reservation.ResourceService/list_resource_groups",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "reservation.ResourceService",
+                "list_resource_groups",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod reservation_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
+    #[async_trait]
+    pub trait ReservationService: Send + Sync + 'static {
+        /// make a reservation
+        async fn reserve(
+            &self,
+            request: tonic::Request<super::ReserveRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
+        /// make several reservations in one transaction, if any of them fails, none is made
+        async fn reserve_batch(
+            &self,
+            request: tonic::Request<super::ReserveBatchRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveBatchResponse>, tonic::Status>;
+        /// make a recurring reservation, occurrences conflicting with existing reservations are skipped. it fails
+        /// with FAILED_PRECONDITION if every occurrence conflicts
+        async fn reserve_recurring(
+            &self,
+            request: tonic::Request<super::ReserveRecurringRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveRecurringResponse>, tonic::Status>;
+        /// reserve the window on a free resource of a group, picked by the strategy. fails with FAILED_PRECONDITION if
+        /// every resource of the group is taken
+        async fn reserve_any(
+            &self,
+            request: tonic::Request<super::ReserveAnyRequest>,
+        ) -> std::result::Result<tonic::Response<super::ReserveAnyResponse>, tonic::Status>;
+        /// queue for a taken window, the entry is promoted to a pending reservation once the window is freed, unless it
+        /// breaks the booking rules of the resource by then. fails with FAILED_PRECONDITION if the window is free
+        async fn join_waitlist(
+            &self,
+            request: tonic::Request<super::JoinWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::JoinWaitlistResponse>, tonic::Status>;
+        /// leave a waitlist, the removed entry is returned
+        async fn leave_waitlist(
+            &self,
+            request: tonic::Request<super::LeaveWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::LeaveWaitlistResponse>, tonic::Status>;
+        /// list the waiting entries of a resource
+        async fn list_waitlist(
+            &self,
+            request: tonic::Request<super::ListWaitlistRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListWaitlistResponse>, tonic::Status>;
+        /// confirm a pending reservation, a confirmed reservation is returned unchanged. other status fails with FAILED_PRECONDITION
+        async fn confirm(
+            &self,
+            request: tonic::Request<super::ConfirmRequest>,
+        ) -> std::result::Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
+        /// move a reservation to another status, illegal transitions fail with FAILED_PRECONDITION
+        async fn transition(
+            &self,
+            request: tonic::Request<super::TransitionRequest>,
+        ) -> std::result::Result<tonic::Response<super::TransitionResponse>, tonic::Status>;
+        /// update the reservation note
+        async fn update(
+            &self,
+            request: tonic::Request<super::UpdateRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
+        /// move a reservation to another time window, keeping its id. a cancelled reservation fails with NOT_FOUND
+        async fn reschedule(
+            &self,
+            request: tonic::Request<super::RescheduleRequest>,
+        ) -> std::result::Result<tonic::Response<super::RescheduleResponse>, tonic::Status>;
+        /// move a reservation to another resource, keeping its id and time window. a cancelled reservation fails with NOT_FOUND
+        async fn transfer(
+            &self,
+            request: tonic::Request<super::TransferRequest>,
+        ) -> std::result::Result<tonic::Response<super::TransferResponse>, tonic::Status>;
+        /// cancel a reservation, it is kept with the cancelled status for history
+        async fn cancel(
+            &self,
+            request: tonic::Request<super::CancelRequest>,
+        ) -> std::result::Result<tonic::Response<super::CancelResponse>, tonic::Status>;
+        /// cancel all the occurrences of a recurring reservation, use cancel for a single occurrence
+        async fn cancel_series(
+            &self,
+            request: tonic::Request<super::CancelSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::CancelSeriesResponse>, tonic::Status>;
+        /// update the note of all the occurrences of a recurring reservation, or move them all
+        async fn update_series(
+            &self,
+            request: tonic::Request<super::UpdateSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateSeriesResponse>, tonic::Status>;
+        /// admin only, needs the `authorization: Bearer <admin_token>` metadata: take a resource offline for a window for
+        /// all users. fails with PERMISSION_DENIED for other callers, so do transition, reschedule, transfer and cancel of a
+        /// blackout
+        async fn blackout(
+            &self,
+            request: tonic::Request<super::BlackoutRequest>,
+        ) -> std::result::Result<tonic::Response<super::BlackoutResponse>, tonic::Status>;
+        /// find the free slots of resources in a window
+        async fn availability(
+            &self,
+            request: tonic::Request<super::AvailabilityRequest>,
+        ) -> std::result::Result<tonic::Response<super::AvailabilityResponse>, tonic::Status>;
+        /// get a reservation by id
+        async fn get(
+            &self,
+            request: tonic::Request<super::GetRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status>;
+        /// Server streaming response type for the query method.
+        type queryStream: futures_core::Stream<Item = std::result::Result<super::Reservation, tonic::Status>>
+            + Send
+            + 'static;
+        /// query reservations by resource id, user id, status, start and end time
+        async fn query(
+            &self,
+            request: tonic::Request<super::QueryRequest>,
+        ) -> std::result::Result<tonic::Response<Self::queryStream>, tonic::Status>;
+        /// filter reservations order by reservation id
+        async fn filter(
+            &self,
+            request: tonic::Request<super::FilterRequest>,
+        ) -> std::result::Result<tonic::Response<super::FilterResponse>, tonic::Status>;
+        /// Server streaming response type for the listen method.
+        type listenStream: futures_core::Stream<Item = std::result::Result<super::ListenResponse, tonic::Status>>
+            + Send
+            + 'static;
+        /// another system could monitor newly added/confirmed/cancelled reservations
+        async fn listen(
+            &self,
+            request: tonic::Request<super::ListenRequest>,
+        ) -> std::result::Result<tonic::Response<Self::listenStream>, tonic::Status>;
+        /// acknowledge processed changes, so a named consumer can resume after reconnecting
+        async fn ack(
+            &self,
+            request: tonic::Request<super::AckRequest>,
+        ) -> std::result::Result<tonic::Response<super::AckResponse>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct ReservationServiceServer<T: ReservationService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: ReservationService> ReservationServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
+    where
+        T: ReservationService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/reservation.ReservationService/reserve" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::ReserveRequest> for reserveSvc<T> {
+                        type Response = super::ReserveResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).reserve(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserveSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_batch" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_batchSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveBatchRequest>
+                        for reserve_batchSvc<T>
+                    {
+                        type Response = super::ReserveBatchResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveBatchRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).reserve_batch(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_batchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_recurring" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_recurringSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveRecurringRequest>
+                        for reserve_recurringSvc<T>
+                    {
+                        type Response = super::ReserveRecurringResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveRecurringRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).reserve_recurring(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_recurringSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reserve_any" => {
+                    #[allow(non_camel_case_types)]
+                    struct reserve_anySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ReserveAnyRequest>
+                        for reserve_anySvc<T>
+                    {
+                        type Response = super::ReserveAnyResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReserveAnyRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).reserve_any(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = reserve_anySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/join_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct join_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::JoinWaitlistRequest>
+                        for join_waitlistSvc<T>
+                    {
+                        type Response = super::JoinWaitlistResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::JoinWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).join_waitlist(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = join_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/leave_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct leave_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::LeaveWaitlistRequest>
+                        for leave_waitlistSvc<T>
+                    {
+                        type Response = super::LeaveWaitlistResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::LeaveWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).leave_waitlist(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = leave_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/list_waitlist" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_waitlistSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::ListWaitlistRequest>
+                        for list_waitlistSvc<T>
+                    {
+                        type Response = super::ListWaitlistResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListWaitlistRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).list_waitlist(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_waitlistSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/confirm" => {
+                    #[allow(non_camel_case_types)]
+                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::ConfirmRequest> for confirmSvc<T> {
+                        type Response = super::ConfirmResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ConfirmRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).confirm(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = confirmSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/transition" => {
+                    #[allow(non_camel_case_types)]
+                    struct transitionSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::TransitionRequest> for transitionSvc<T>
+                    {
+                        type Response = super::TransitionResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TransitionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).transition(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = transitionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update" => {
+                    #[allow(non_camel_case_types)]
+                    struct updateSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::UpdateRequest> for updateSvc<T> {
+                        type Response = super::UpdateResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).update(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = updateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/reschedule" => {
+                    #[allow(non_camel_case_types)]
+                    struct rescheduleSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::RescheduleRequest> for rescheduleSvc<T>
+                    {
+                        type Response = super::RescheduleResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RescheduleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).reschedule(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = rescheduleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/transfer" => {
+                    #[allow(non_camel_case_types)]
+                    struct transferSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::TransferRequest> for transferSvc<T> {
+                        type Response = super::TransferResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TransferRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).transfer(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = transferSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::CancelRequest> for cancelSvc<T> {
+                        type Response = super::CancelResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).cancel(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancelSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/cancel_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct cancel_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::CancelSeriesRequest>
+                        for cancel_seriesSvc<T>
+                    {
+                        type Response = super::CancelSeriesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).cancel_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = cancel_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/update_series" => {
+                    #[allow(non_camel_case_types)]
+                    struct update_seriesSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::UpdateSeriesRequest>
+                        for update_seriesSvc<T>
+                    {
+                        type Response = super::UpdateSeriesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).update_series(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = update_seriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/blackout" => {
+                    #[allow(non_camel_case_types)]
+                    struct blackoutSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::BlackoutRequest> for blackoutSvc<T> {
+                        type Response = super::BlackoutResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::BlackoutRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).blackout(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = blackoutSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/availability" => {
+                    #[allow(non_camel_case_types)]
+                    struct availabilitySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::UnaryService<super::AvailabilityRequest>
+                        for availabilitySvc<T>
+                    {
+                        type Response = super::AvailabilityResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AvailabilityRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).availability(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = availabilitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/get" => {
+                    #[allow(non_camel_case_types)]
+                    struct getSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
+                        type Response = super::GetResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = getSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/query" => {
+                    #[allow(non_camel_case_types)]
+                    struct querySvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::ServerStreamingService<super::QueryRequest> for querySvc<T>
+                    {
+                        type Response = super::Reservation;
+                        type ResponseStream = T::queryStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).query(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = querySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/filter" => {
+                    #[allow(non_camel_case_types)]
+                    struct filterSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::FilterRequest> for filterSvc<T> {
+                        type Response = super::FilterResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FilterRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).filter(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = filterSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/listen" => {
+                    #[allow(non_camel_case_types)]
+                    struct listenSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService>
+                        tonic::server::ServerStreamingService<super::ListenRequest>
+                        for listenSvc<T>
+                    {
+                        type Response = super::ListenResponse;
+                        type ResponseStream = T::listenStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListenRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).listen(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = listenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ReservationService/ack" => {
+                    #[allow(non_camel_case_types)]
+                    struct ackSvc<T: ReservationService>(pub Arc<T>);
+                    impl<T: ReservationService> tonic::server::UnaryService<super::AckRequest> for ackSvc<T> {
+                        type Response = super::AckResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AckRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).ack(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ackSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: ReservationService> Clone for ReservationServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: ReservationService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
         }
     }
+    impl<T: ReservationService> tonic::server::NamedService for ReservationServiceServer<T> {
+        const NAME: &'static str = "reservation.ReservationService";
+    }
 }
 /// Generated server implementations.
-pub mod reservation_service_server {
+pub mod resource_service_server {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
     use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with ReservationServiceServer.
+    /// Generated trait containing gRPC methods that should be implemented for use with ResourceServiceServer.
     #[async_trait]
-    pub trait ReservationService: Send + Sync + 'static {
-        /// make a reservation
-        async fn reserve(
+    pub trait ResourceService: Send + Sync + 'static {
+        /// add a resource to the catalog
+        async fn create_resource(
             &self,
-            request: tonic::Request<super::ReserveRequest>,
-        ) -> std::result::Result<tonic::Response<super::ReserveResponse>, tonic::Status>;
-        /// confirm a pending reservation, if reservation is not pending, do nothing
-        async fn confirm(
+            request: tonic::Request<super::CreateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateResourceResponse>, tonic::Status>;
+        /// update a resource, the id can't be changed
+        async fn update_resource(
             &self,
-            request: tonic::Request<super::ConfirmRequest>,
-        ) -> std::result::Result<tonic::Response<super::ConfirmResponse>, tonic::Status>;
-        /// update the reservation note
-        async fn update(
+            request: tonic::Request<super::UpdateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResourceResponse>, tonic::Status>;
+        /// get a resource by id
+        async fn get_resource(
             &self,
-            request: tonic::Request<super::UpdateRequest>,
-        ) -> std::result::Result<tonic::Response<super::UpdateResponse>, tonic::Status>;
-        /// cancel a reservation
-        async fn cancel(
+            request: tonic::Request<super::GetResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetResourceResponse>, tonic::Status>;
+        /// list the resources of the catalog
+        async fn list_resources(
             &self,
-            request: tonic::Request<super::CancelRequest>,
-        ) -> std::result::Result<tonic::Response<super::CancelResponse>, tonic::Status>;
-        /// get a reservation by id
-        async fn get(
+            request: tonic::Request<super::ListResourcesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListResourcesResponse>, tonic::Status>;
+        /// stop accepting reservations on a resource, existing reservations are kept
+        async fn deactivate_resource(
             &self,
-            request: tonic::Request<super::GetRequest>,
-        ) -> std::result::Result<tonic::Response<super::GetResponse>, tonic::Status>;
-        /// Server streaming response type for the query method.
-        type queryStream: futures_core::Stream<Item = std::result::Result<super::Reservation, tonic::Status>>
-            + Send
-            + 'static;
-        /// query reservations by resource id, user id, status, start and end time
-        async fn query(
+            request: tonic::Request<super::DeactivateResourceRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeactivateResourceResponse>, tonic::Status>;
+        /// replace the rules checked when reserving a resource, e.g. opening hours
+        async fn set_booking_rules(
             &self,
-            request: tonic::Request<super::QueryRequest>,
-        ) -> std::result::Result<tonic::Response<Self::queryStream>, tonic::Status>;
-        /// filter reservations order by reservation id
-        async fn filter(
+            request: tonic::Request<super::SetBookingRulesRequest>,
+        ) -> std::result::Result<tonic::Response<super::SetBookingRulesResponse>, tonic::Status>;
+        /// get the booking rules of a resource, a resource without rules has empty ones
+        async fn get_booking_rules(
             &self,
-            request: tonic::Request<super::FilterRequest>,
-        ) -> std::result::Result<tonic::Response<super::FilterResponse>, tonic::Status>;
-        /// Server streaming response type for the listen method.
-        type listenStream: futures_core::Stream<Item = std::result::Result<super::Reservation, tonic::Status>>
-            + Send
-            + 'static;
-        /// another system could monitor newly added/confirmed/cancelled reservations
-        async fn listen(
+            request: tonic::Request<super::GetBookingRulesRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetBookingRulesResponse>, tonic::Status>;
+        /// add a group of resources
+        async fn create_resource_group(
             &self,
-            request: tonic::Request<super::ListenRequest>,
-        ) -> std::result::Result<tonic::Response<Self::listenStream>, tonic::Status>;
+            request: tonic::Request<super::CreateResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::CreateResourceGroupResponse>, tonic::Status>;
+        /// replace the name and the members of a group
+        async fn update_resource_group(
+            &self,
+            request: tonic::Request<super::UpdateResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::UpdateResourceGroupResponse>, tonic::Status>;
+        /// get a group by id
+        async fn get_resource_group(
+            &self,
+            request: tonic::Request<super::GetResourceGroupRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetResourceGroupResponse>, tonic::Status>;
+        /// list the groups ordered by id
+        async fn list_resource_groups(
+            &self,
+            request: tonic::Request<super::ListResourceGroupsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListResourceGroupsResponse>, tonic::Status>;
     }
     #[derive(Debug)]
-    pub struct ReservationServiceServer<T: ReservationService> {
+    pub struct ResourceServiceServer<T: ResourceService> {
         inner: _Inner<T>,
         accept_compression_encodings: EnabledCompressionEncodings,
         send_compression_encodings: EnabledCompressionEncodings,
@@ -589,7 +3143,7 @@ pub mod reservation_service_server {
         max_encoding_message_size: Option<usize>,
     }
     struct _Inner<T>(Arc<T>);
-    impl<T: ReservationService> ReservationServiceServer<T> {
+    impl<T: ResourceService> ResourceServiceServer<T> {
         pub fn new(inner: T) -> Self {
             Self::from_arc(Arc::new(inner))
         }
@@ -638,9 +3192,9 @@ pub mod reservation_service_server {
             self
         }
     }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for ReservationServiceServer<T>
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for ResourceServiceServer<T>
     where
-        T: ReservationService,
+        T: ResourceService,
         B: Body + Send + 'static,
         B::Error: Into<StdError> + Send + 'static,
     {
@@ -656,18 +3210,21 @@ pub mod reservation_service_server {
         fn call(&mut self, req: http::Request<B>) -> Self::Future {
             let inner = self.inner.clone();
             match req.uri().path() {
-                "/reservation.ReservationService/reserve" => {
+                "/reservation.ResourceService/create_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct reserveSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ReserveRequest> for reserveSvc<T> {
-                        type Response = super::ReserveResponse;
+                    struct create_resourceSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::CreateResourceRequest>
+                        for create_resourceSvc<T>
+                    {
+                        type Response = super::CreateResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ReserveRequest>,
+                            request: tonic::Request<super::CreateResourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).reserve(request).await };
+                            let fut = async move { (*inner).create_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -678,7 +3235,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = reserveSvc(inner);
+                        let method = create_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -694,18 +3251,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/confirm" => {
+                "/reservation.ResourceService/update_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct confirmSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::ConfirmRequest> for confirmSvc<T> {
-                        type Response = super::ConfirmResponse;
+                    struct update_resourceSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::UpdateResourceRequest>
+                        for update_resourceSvc<T>
+                    {
+                        type Response = super::UpdateResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ConfirmRequest>,
+                            request: tonic::Request<super::UpdateResourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).confirm(request).await };
+                            let fut = async move { (*inner).update_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -716,7 +3276,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = confirmSvc(inner);
+                        let method = update_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -732,18 +3292,20 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/update" => {
+                "/reservation.ResourceService/get_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct updateSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::UpdateRequest> for updateSvc<T> {
-                        type Response = super::UpdateResponse;
+                    struct get_resourceSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService> tonic::server::UnaryService<super::GetResourceRequest>
+                        for get_resourceSvc<T>
+                    {
+                        type Response = super::GetResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::UpdateRequest>,
+                            request: tonic::Request<super::GetResourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).update(request).await };
+                            let fut = async move { (*inner).get_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -754,7 +3316,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = updateSvc(inner);
+                        let method = get_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -770,18 +3332,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/cancel" => {
+                "/reservation.ResourceService/list_resources" => {
                     #[allow(non_camel_case_types)]
-                    struct cancelSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::CancelRequest> for cancelSvc<T> {
-                        type Response = super::CancelResponse;
+                    struct list_resourcesSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::ListResourcesRequest>
+                        for list_resourcesSvc<T>
+                    {
+                        type Response = super::ListResourcesResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::CancelRequest>,
+                            request: tonic::Request<super::ListResourcesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).cancel(request).await };
+                            let fut = async move { (*inner).list_resources(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -792,7 +3357,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = cancelSvc(inner);
+                        let method = list_resourcesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -808,18 +3373,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/get" => {
+                "/reservation.ResourceService/deactivate_resource" => {
                     #[allow(non_camel_case_types)]
-                    struct getSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::GetRequest> for getSvc<T> {
-                        type Response = super::GetResponse;
+                    struct deactivate_resourceSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::DeactivateResourceRequest>
+                        for deactivate_resourceSvc<T>
+                    {
+                        type Response = super::DeactivateResourceResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::GetRequest>,
+                            request: tonic::Request<super::DeactivateResourceRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).get(request).await };
+                            let fut = async move { (*inner).deactivate_resource(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -830,7 +3398,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = getSvc(inner);
+                        let method = deactivate_resourceSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -846,22 +3414,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/query" => {
+                "/reservation.ResourceService/set_booking_rules" => {
                     #[allow(non_camel_case_types)]
-                    struct querySvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::QueryRequest> for querySvc<T>
+                    struct set_booking_rulesSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::SetBookingRulesRequest>
+                        for set_booking_rulesSvc<T>
                     {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::queryStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        type Response = super::SetBookingRulesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::QueryRequest>,
+                            request: tonic::Request<super::SetBookingRulesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).query(request).await };
+                            let fut = async move { (*inner).set_booking_rules(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -872,7 +3439,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = querySvc(inner);
+                        let method = set_booking_rulesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -883,23 +3450,26 @@ pub mod reservation_service_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/filter" => {
+                "/reservation.ResourceService/get_booking_rules" => {
                     #[allow(non_camel_case_types)]
-                    struct filterSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService> tonic::server::UnaryService<super::FilterRequest> for filterSvc<T> {
-                        type Response = super::FilterResponse;
+                    struct get_booking_rulesSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::GetBookingRulesRequest>
+                        for get_booking_rulesSvc<T>
+                    {
+                        type Response = super::GetBookingRulesResponse;
                         type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FilterRequest>,
+                            request: tonic::Request<super::GetBookingRulesRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).filter(request).await };
+                            let fut = async move { (*inner).get_booking_rules(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -910,7 +3480,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = filterSvc(inner);
+                        let method = get_booking_rulesSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -926,23 +3496,21 @@ pub mod reservation_service_server {
                     };
                     Box::pin(fut)
                 }
-                "/reservation.ReservationService/listen" => {
+                "/reservation.ResourceService/create_resource_group" => {
                     #[allow(non_camel_case_types)]
-                    struct listenSvc<T: ReservationService>(pub Arc<T>);
-                    impl<T: ReservationService>
-                        tonic::server::ServerStreamingService<super::ListenRequest>
-                        for listenSvc<T>
+                    struct create_resource_groupSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::CreateResourceGroupRequest>
+                        for create_resource_groupSvc<T>
                     {
-                        type Response = super::Reservation;
-                        type ResponseStream = T::listenStream;
-                        type Future =
-                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        type Response = super::CreateResourceGroupResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::ListenRequest>,
+                            request: tonic::Request<super::CreateResourceGroupRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut = async move { (*inner).listen(request).await };
+                            let fut = async move { (*inner).create_resource_group(request).await };
                             Box::pin(fut)
                         }
                     }
@@ -953,7 +3521,7 @@ pub mod reservation_service_server {
                     let inner = self.inner.clone();
                     let fut = async move {
                         let inner = inner.0;
-                        let method = listenSvc(inner);
+                        let method = create_resource_groupSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -964,7 +3532,130 @@ pub mod reservation_service_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.server_streaming(method, req).await;
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ResourceService/update_resource_group" => {
+                    #[allow(non_camel_case_types)]
+                    struct update_resource_groupSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::UpdateResourceGroupRequest>
+                        for update_resource_groupSvc<T>
+                    {
+                        type Response = super::UpdateResourceGroupResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateResourceGroupRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).update_resource_group(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = update_resource_groupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ResourceService/get_resource_group" => {
+                    #[allow(non_camel_case_types)]
+                    struct get_resource_groupSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::GetResourceGroupRequest>
+                        for get_resource_groupSvc<T>
+                    {
+                        type Response = super::GetResourceGroupResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetResourceGroupRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).get_resource_group(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = get_resource_groupSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/reservation.ResourceService/list_resource_groups" => {
+                    #[allow(non_camel_case_types)]
+                    struct list_resource_groupsSvc<T: ResourceService>(pub Arc<T>);
+                    impl<T: ResourceService>
+                        tonic::server::UnaryService<super::ListResourceGroupsRequest>
+                        for list_resource_groupsSvc<T>
+                    {
+                        type Response = super::ListResourceGroupsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListResourceGroupsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move { (*inner).list_resource_groups(request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = list_resource_groupsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
@@ -980,7 +3671,7 @@ pub mod reservation_service_server {
             }
         }
     }
-    impl<T: ReservationService> Clone for ReservationServiceServer<T> {
+    impl<T: ResourceService> Clone for ResourceServiceServer<T> {
         fn clone(&self) -> Self {
             let inner = self.inner.clone();
             Self {
@@ -992,7 +3683,7 @@ pub mod reservation_service_server {
             }
         }
     }
-    impl<T: ReservationService> Clone for _Inner<T> {
+    impl<T: ResourceService> Clone for _Inner<T> {
         fn clone(&self) -> Self {
             Self(Arc::clone(&self.0))
         }
@@ -1002,7 +3693,7 @@ pub mod reservation_service_server {
             write!(f, "{:?}", self.0)
         }
     }
-    impl<T: ReservationService> tonic::server::NamedService for ReservationServiceServer<T> {
-        const NAME: &'static str = "reservation.ReservationService";
+    impl<T: ResourceService> tonic::server::NamedService for ResourceServiceServer<T> {
+        const NAME: &'static str = "reservation.ResourceService";
     }
 }
@@ -0,0 +1,187 @@
+use crate::{
+    convert_to_timestamp, convert_to_utc_time, AvailabilityRequest, Error, FreeSlot, ToSql,
+    Validator,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, FromRow, Postgres, QueryBuilder, Row};
+
+use super::validate_range;
+
+impl Validator for AvailabilityRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_ids.is_empty() {
+            return Err(Error::InvalidResourceId(String::new()));
+        }
+
+        if let Some(rid) = self.resource_ids.iter().find(|rid| rid.is_empty()) {
+            return Err(Error::InvalidResourceId(rid.clone()));
+        }
+
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+
+        if self.min_duration < 0 {
+            return Err(Error::InvalidDuration(self.min_duration));
+        }
+
+        Ok(())
+    }
+}
+
+impl ToSql for AvailabilityRequest {
+    // only the active resources of the catalog are looked at, unknown or inactive ones have no
+    // free slot. free slots are the window minus the sub-windows in which the resource is fully booked (see
+    // rsvp.saturated) or blocked by its ancestors and descendants (see rsvp.blocked_by_related),
+    // computed with multiranges for every resource. the blocked windows include
+    // the buffers, and a new reservation needs room for its own buffers: the window is widened by
+    // the buffers first, then every free piece is narrowed by them
+    fn to_sql(&self) -> QueryBuilder<'static, Postgres> {
+        let mut builder = QueryBuilder::new(
+            "SELECT r.resource_id, lower(f.slot) AS start, upper(f.slot) AS end FROM unnest(",
+        );
+        builder
+            .push_bind(self.resource_ids.clone())
+            .push(
+                "::text[]) AS r(resource_id) JOIN rsvp.resources res ON res.id = r.resource_id AND res.active \
+                CROSS JOIN LATERAL (SELECT res.buffer_before * interval '1 second' AS before, \
+                res.buffer_after * interval '1 second' AS after) AS b CROSS JOIN LATERAL (SELECT tstzrange(",
+            )
+            .push_bind(self.start.as_ref().map(convert_to_utc_time))
+            .push(", ")
+            .push_bind(self.end.as_ref().map(convert_to_utc_time))
+            .push(
+                ") AS window_range) AS w CROSS JOIN LATERAL (SELECT tstzrange(lower(w.window_range) - b.before, \
+                upper(w.window_range) + b.after) AS padded_range) AS p CROSS JOIN LATERAL unnest(\
+                tstzmultirange(p.padded_range) - rsvp.saturated(r.resource_id, p.padded_range) \
+                - rsvp.blocked_by_related(r.resource_id, p.padded_range)) AS u(piece) \
+                CROSS JOIN LATERAL (SELECT w.window_range * tstzrange(lower(u.piece) + b.before, \
+                greatest(lower(u.piece) + b.before, upper(u.piece) - b.after)) AS slot) AS f \
+                WHERE NOT isempty(f.slot) AND upper(f.slot) - lower(f.slot) >= ",
+            )
+            .push_bind(self.min_duration)
+            .push(" * interval '1 second' ORDER BY r.resource_id, lower(f.slot)");
+        builder
+    }
+}
+
+/// pick the count nearest windows lasting as long as [start, end] from the free slots, nearest
+/// first. every slot gives at most one window, the one closest to start
+pub fn nearest_windows(
+    slots: Vec<FreeSlot>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    count: usize,
+) -> Vec<FreeSlot> {
+    let duration = end - start;
+    let mut windows: Vec<_> = slots
+        .into_iter()
+        .filter_map(|slot| {
+            let slot_start = convert_to_utc_time(slot.start.as_ref()?);
+            let slot_end = convert_to_utc_time(slot.end.as_ref()?);
+            if slot_end - slot_start < duration {
+                return None;
+            }
+            let window_start = start.clamp(slot_start, slot_end - duration);
+            let distance = (window_start - start).num_seconds().abs();
+            Some((distance, slot.resource_id, window_start))
+        })
+        .collect();
+
+    windows.sort();
+    windows
+        .into_iter()
+        .take(count)
+        .map(|(_, resource_id, window_start)| FreeSlot {
+            resource_id,
+            start: Some(convert_to_timestamp(&window_start)),
+            end: Some(convert_to_timestamp(&(window_start + duration))),
+        })
+        .collect()
+}
+
+impl FromRow<'_, PgRow> for FreeSlot {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let start: DateTime<Utc> = row.try_get("start")?;
+        let end: DateTime<Utc> = row.try_get("end")?;
+
+        Ok(Self {
+            resource_id: row.try_get("resource_id")?,
+            start: Some(convert_to_timestamp(&start)),
+            end: Some(convert_to_timestamp(&end)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn availability(resource_ids: &[&str], min_duration: i64) -> AvailabilityRequest {
+        AvailabilityRequest {
+            resource_ids: resource_ids.iter().map(|rid| rid.to_string()).collect(),
+            start: Some("2023-12-25T00:00:00Z".parse().unwrap()),
+            end: Some("2023-12-26T00:00:00Z".parse().unwrap()),
+            min_duration,
+        }
+    }
+
+    #[test]
+    fn availability_request_should_be_validated() {
+        assert!(availability(&["room-1", "room-2"], 3600).validate().is_ok());
+        assert_eq!(
+            availability(&[], 0).validate(),
+            Err(Error::InvalidResourceId("".into()))
+        );
+        assert_eq!(
+            availability(&["room-1", ""], 0).validate(),
+            Err(Error::InvalidResourceId("".into()))
+        );
+        assert_eq!(
+            availability(&["room-1"], -1).validate(),
+            Err(Error::InvalidDuration(-1))
+        );
+
+        let mut req = availability(&["room-1"], 0);
+        req.end = None;
+        assert_eq!(req.validate(), Err(Error::InvalidTime));
+    }
+
+    #[test]
+    fn availability_should_bind_all_values() {
+        let sql = availability(&["room'; DROP TABLE rsvp.reservations; --"], 0).to_sql();
+        assert!(sql.sql().contains("unnest($1::text[])"));
+        assert!(sql.sql().contains("tstzrange($2, $3)"));
+        assert!(sql.sql().contains(">= $4 * interval '1 second'"));
+        assert!(!sql.sql().contains("DROP TABLE"));
+    }
+
+    fn slot(rid: &str, start: &str, end: &str) -> FreeSlot {
+        FreeSlot {
+            resource_id: rid.to_string(),
+            start: Some(start.parse().unwrap()),
+            end: Some(end.parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn nearest_windows_should_be_sorted_by_distance() {
+        let slots = vec![
+            slot("room-1", "2023-12-25T00:00:00Z", "2023-12-25T10:00:00Z"),
+            slot("room-1", "2023-12-25T11:00:00Z", "2023-12-25T11:30:00Z"),
+            slot("room-1", "2023-12-25T14:00:00Z", "2023-12-26T00:00:00Z"),
+            slot("room-2", "2023-12-25T11:00:00Z", "2023-12-25T13:00:00Z"),
+        ];
+        let start = "2023-12-25T11:00:00Z".parse().unwrap();
+        let end = "2023-12-25T12:00:00Z".parse().unwrap();
+
+        let windows = nearest_windows(slots, start, end, 3);
+        assert_eq!(
+            windows,
+            vec![
+                // the same window on the sibling resource is free
+                slot("room-2", "2023-12-25T11:00:00Z", "2023-12-25T12:00:00Z"),
+                slot("room-1", "2023-12-25T09:00:00Z", "2023-12-25T10:00:00Z"),
+                slot("room-1", "2023-12-25T14:00:00Z", "2023-12-25T15:00:00Z"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,308 @@
+use crate::{
+    convert_to_utc_time, BookingRules, Error, Normalizer, OpeningHours, PolicyViolationReason,
+    Reservation, Validator,
+};
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use chrono_tz::Tz;
+use sqlx::{postgres::PgRow, types::Json, FromRow, Row};
+
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+impl BookingRules {
+    /// a resource without rules accepts any valid reservation
+    pub fn new(resource_id: impl Into<String>) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+            timezone: "UTC".into(),
+            ..Default::default()
+        }
+    }
+
+    /// check a valid reservation against the rules, now is the time the reservation is made at
+    pub fn check(&self, rsvp: &Reservation, now: DateTime<Utc>) -> Result<(), Error> {
+        let start = convert_to_utc_time(rsvp.start.as_ref().ok_or(Error::InvalidTime)?);
+        let end = convert_to_utc_time(rsvp.end.as_ref().ok_or(Error::InvalidTime)?);
+
+        let duration = (end - start).num_seconds();
+        if self.min_duration > 0 && duration < self.min_duration {
+            return Err(Error::PolicyViolation(PolicyViolationReason::TooShort));
+        }
+        if self.max_duration > 0 && duration > self.max_duration {
+            return Err(Error::PolicyViolation(PolicyViolationReason::TooLong));
+        }
+        if self.max_lead_time > 0 && (start - now).num_seconds() > self.max_lead_time {
+            return Err(Error::PolicyViolation(PolicyViolationReason::TooFarAhead));
+        }
+        if !self.opening_hours.is_empty() && !self.is_open(start, end)? {
+            return Err(Error::PolicyViolation(
+                PolicyViolationReason::OutsideOpeningHours,
+            ));
+        }
+
+        Ok(())
+    }
+
+    // the reservation must fit in one window of the day it starts on, in the time zone of the rules
+    fn is_open(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<bool, Error> {
+        let tz = self.get_timezone()?;
+        let start = start.with_timezone(&tz);
+        let end = end.with_timezone(&tz);
+
+        let day = start.date_naive();
+        let weekday = day.weekday().number_from_monday() as i32;
+        let opens_at = start.num_seconds_from_midnight() as i64;
+        let closes_at = (end.date_naive() - day).num_days() * MINUTES_PER_DAY as i64 * 60
+            + end.num_seconds_from_midnight() as i64;
+
+        Ok(self.opening_hours.iter().any(|hours| {
+            hours.weekday == weekday
+                && hours.opens as i64 * 60 <= opens_at
+                && closes_at <= hours.closes as i64 * 60
+        }))
+    }
+
+    fn get_timezone(&self) -> Result<Tz, Error> {
+        if self.timezone.is_empty() {
+            return Ok(Tz::UTC);
+        }
+        self.timezone
+            .parse()
+            .map_err(|_| Error::InvalidBookingRules(format!("unknown timezone {}", self.timezone)))
+    }
+}
+
+impl Validator for BookingRules {
+    fn validate(&self) -> Result<(), Error> {
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+
+        self.get_timezone()?;
+
+        for hours in &self.opening_hours {
+            if !(1..=7).contains(&hours.weekday) {
+                return Err(Error::InvalidBookingRules(format!(
+                    "invalid weekday {}",
+                    hours.weekday
+                )));
+            }
+            if hours.opens < 0 || hours.opens >= hours.closes || hours.closes > MINUTES_PER_DAY {
+                return Err(Error::InvalidBookingRules(format!(
+                    "invalid opening hours {}-{}",
+                    hours.opens, hours.closes
+                )));
+            }
+        }
+
+        for duration in [self.min_duration, self.max_duration, self.max_lead_time] {
+            if duration < 0 {
+                return Err(Error::InvalidDuration(duration));
+            }
+        }
+        if self.max_duration > 0 && self.min_duration > self.max_duration {
+            return Err(Error::InvalidDuration(self.min_duration));
+        }
+
+        Ok(())
+    }
+}
+
+impl Normalizer for BookingRules {
+    fn do_normalize(&mut self) {
+        if self.timezone.is_empty() {
+            self.timezone = "UTC".into();
+        }
+        self.opening_hours
+            .sort_by_key(|hours| (hours.weekday, hours.opens));
+    }
+}
+
+impl FromRow<'_, PgRow> for BookingRules {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let opening_hours: Json<Vec<OpeningHours>> = row.get("opening_hours");
+        Ok(Self {
+            resource_id: row.get("resource_id"),
+            timezone: row.get("timezone"),
+            opening_hours: opening_hours.0,
+            min_duration: row.get("min_duration"),
+            max_duration: row.get("max_duration"),
+            max_lead_time: row.get("max_lead_time"),
+        })
+    }
+}
+
+impl OpeningHours {
+    /// opens and closes are "HH:MM", closes may be "24:00"
+    pub fn new(weekday: i32, opens: &str, closes: &str) -> Result<Self, Error> {
+        Ok(Self {
+            weekday,
+            opens: parse_minutes(opens)?,
+            closes: parse_minutes(closes)?,
+        })
+    }
+}
+
+fn parse_minutes(s: &str) -> Result<i32, Error> {
+    let invalid = || Error::InvalidBookingRules(format!("invalid time of day {}", s));
+    let (hours, minutes) = s.split_once(':').ok_or_else(invalid)?;
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if !(0..60).contains(&minutes) || hours < 0 || hours * 60 + minutes > MINUTES_PER_DAY {
+        return Err(invalid());
+    }
+    Ok(hours * 60 + minutes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn office_rules() -> BookingRules {
+        BookingRules {
+            timezone: "Europe/Paris".into(),
+            opening_hours: (1..=5)
+                .map(|weekday| OpeningHours::new(weekday, "09:00", "18:00").unwrap())
+                .collect(),
+            min_duration: 30 * 60,
+            max_duration: 4 * 3600,
+            max_lead_time: 30 * 86400,
+            ..BookingRules::new("meeting-room-1")
+        }
+    }
+
+    fn rsvp(start: &str, end: &str) -> Reservation {
+        Reservation::new_pending(
+            "tyr",
+            "meeting-room-1",
+            start.parse().unwrap(),
+            end.parse().unwrap(),
+            "weekly review",
+        )
+    }
+
+    fn violation(rules: &BookingRules, start: &str, end: &str) -> Option<PolicyViolationReason> {
+        let now = "2024-03-01T00:00:00Z".parse().unwrap();
+        match rules.check(&rsvp(start, end), now) {
+            Ok(()) => None,
+            Err(Error::PolicyViolation(reason)) => Some(reason),
+            Err(e) => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn booking_rules_should_check_reservations() {
+        let rules = office_rules();
+        assert!(rules.validate().is_ok());
+
+        // monday 09:00-10:00 in Paris
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-03-04T09:00:00+0100",
+                "2024-03-04T10:00:00+0100"
+            ),
+            None
+        );
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-03-04T08:30:00+0100",
+                "2024-03-04T10:00:00+0100"
+            ),
+            Some(PolicyViolationReason::OutsideOpeningHours)
+        );
+        // saturday
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-03-09T09:00:00+0100",
+                "2024-03-09T10:00:00+0100"
+            ),
+            Some(PolicyViolationReason::OutsideOpeningHours)
+        );
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-03-04T09:00:00+0100",
+                "2024-03-04T09:15:00+0100"
+            ),
+            Some(PolicyViolationReason::TooShort)
+        );
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-03-04T09:00:00+0100",
+                "2024-03-04T14:00:00+0100"
+            ),
+            Some(PolicyViolationReason::TooLong)
+        );
+        assert_eq!(
+            violation(
+                &rules,
+                "2024-04-08T09:00:00+0200",
+                "2024-04-08T10:00:00+0200"
+            ),
+            Some(PolicyViolationReason::TooFarAhead)
+        );
+    }
+
+    #[test]
+    fn opening_hours_should_follow_daylight_saving_time() {
+        let mut rules = office_rules();
+        rules.max_lead_time = 0;
+        // 09:00 in Paris is 07:00 UTC in summer
+        assert_eq!(
+            violation(&rules, "2024-07-01T07:00:00Z", "2024-07-01T08:00:00Z"),
+            None
+        );
+        assert_eq!(
+            violation(&rules, "2024-07-01T06:30:00Z", "2024-07-01T07:30:00Z"),
+            Some(PolicyViolationReason::OutsideOpeningHours)
+        );
+    }
+
+    #[test]
+    fn opening_hours_may_close_at_midnight() {
+        let mut rules = BookingRules::new("bar-1");
+        rules.opening_hours = vec![OpeningHours::new(5, "18:00", "24:00").unwrap()];
+        assert_eq!(
+            violation(&rules, "2024-03-08T20:00:00Z", "2024-03-09T00:00:00Z"),
+            None
+        );
+        assert_eq!(
+            violation(&rules, "2024-03-08T20:00:00Z", "2024-03-09T00:30:00Z"),
+            Some(PolicyViolationReason::OutsideOpeningHours)
+        );
+    }
+
+    #[test]
+    fn invalid_booking_rules_should_be_rejected() {
+        let mut rules = office_rules();
+        rules.timezone = "Mars/Olympus".into();
+        assert!(matches!(
+            rules.validate(),
+            Err(Error::InvalidBookingRules(_))
+        ));
+
+        let mut rules = office_rules();
+        rules.opening_hours = vec![OpeningHours {
+            weekday: 8,
+            opens: 0,
+            closes: 60,
+        }];
+        assert!(matches!(
+            rules.validate(),
+            Err(Error::InvalidBookingRules(_))
+        ));
+
+        let mut rules = office_rules();
+        rules.min_duration = rules.max_duration + 1;
+        assert_eq!(
+            rules.validate(),
+            Err(Error::InvalidDuration(rules.min_duration))
+        );
+
+        assert!(OpeningHours::new(1, "9", "18:00").is_err());
+        assert!(OpeningHours::new(1, "09:00", "24:30").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    AckRequest, Error, ListenRequest, ListenResponse, Reservation, ReservationUpdateType,
+    RsvpUpdateType, Validator,
+};
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, types::Json, FromRow, Row};
+
+use super::reservation::ReservationJson;
+
+impl Validator for ListenRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(start_id) = self.start_id {
+            if start_id < 0 {
+                return Err(Error::InvalidCursor(start_id));
+            }
+        }
+
+        if self.consumer_id.len() > 64 {
+            return Err(Error::InvalidConsumerId(self.consumer_id.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+impl Validator for AckRequest {
+    fn validate(&self) -> Result<(), Error> {
+        if self.consumer_id.is_empty() || self.consumer_id.len() > 64 {
+            return Err(Error::InvalidConsumerId(self.consumer_id.clone()));
+        }
+
+        if self.change_id < 0 {
+            return Err(Error::InvalidCursor(self.change_id));
+        }
+
+        Ok(())
+    }
+}
+
+// a change row is expected to carry the `change_id`, `op`, `old` and `new` columns of
+// rsvp.reservation_changes, and the bounds of their ranges as `old_start`, `old_end`,
+// `old_blocked_start`, `old_blocked_end` and the same for `new`
+impl FromRow<'_, PgRow> for ListenResponse {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let op: RsvpUpdateType = row.try_get("op")?;
+        let old = decode_reservation(row, "old")?;
+        let new = decode_reservation(row, "new")?;
+
+        // deleted reservation only has the old value
+        let (reservation, old) = match new {
+            Some(new) => (Some(new), old),
+            None => (old, None),
+        };
+
+        Ok(Self {
+            op: ReservationUpdateType::from(op) as i32,
+            reservation,
+            id: row.try_get("change_id")?,
+            old,
+        })
+    }
+}
+
+fn decode_reservation(row: &PgRow, column: &str) -> Result<Option<Reservation>, sqlx::Error> {
+    let value: Option<Json<ReservationJson>> = row.try_get(column)?;
+    let Some(Json(value)) = value else {
+        return Ok(None);
+    };
+
+    let bounds = |start: &str, end: &str| -> Result<Option<_>, sqlx::Error> {
+        let start: Option<DateTime<Utc>> = row.try_get(format!("{}_{}", column, start).as_str())?;
+        let end: Option<DateTime<Utc>> = row.try_get(format!("{}_{}", column, end).as_str())?;
+        Ok(start.zip(end))
+    };
+    let timespan = bounds("start", "end")?
+        .ok_or_else(|| sqlx::Error::Decode(format!("{} has no bounded timespan", column).into()))?;
+    let blocked = bounds("blocked_start", "blocked_end")?;
+
+    Ok(Some(value.into_reservation(timespan, blocked)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listen_request_should_reject_negative_start_id() {
+        let req = ListenRequest {
+            start_id: Some(-1),
+            consumer_id: "billing".into(),
+        };
+        assert_eq!(req.validate(), Err(Error::InvalidCursor(-1)));
+    }
+
+    #[test]
+    fn ack_request_should_require_consumer_id() {
+        let req = AckRequest {
+            consumer_id: "".into(),
+            change_id: 1,
+        };
+        assert_eq!(req.validate(), Err(Error::InvalidConsumerId("".into())));
+        assert!(AckRequest::new("billing", 1).validate().is_ok());
+    }
+}
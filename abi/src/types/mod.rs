@@ -1,15 +1,25 @@
+mod availability;
+mod booking_rules;
+mod listen;
+mod recurrence;
 mod request;
 mod reservation;
 mod reservation_filter;
 mod reservation_query;
 mod reservation_status;
+mod reservation_update_type;
+mod resource;
+mod resource_group;
+mod waitlist;
 
+pub use availability::nearest_windows;
+pub use recurrence::MAX_OCCURRENCES;
 use std::ops::Bound;
 
-use crate::{convert_to_utc_time, Error};
+use crate::{convert_to_utc_time, Error, ReservationStatus};
 use chrono::{DateTime, Utc};
 use prost_types::Timestamp;
-use sqlx::postgres::types::PgRange;
+use sqlx::{postgres::types::PgRange, Postgres, QueryBuilder};
 
 pub fn validate_range(start: Option<&Timestamp>, end: Option<&Timestamp>) -> Result<(), Error> {
     if start.is_none() || end.is_none() {
@@ -36,6 +46,56 @@ pub fn get_timespan(start: Option<&Timestamp>, end: Option<&Timestamp>) -> PgRan
     }
 }
 
+/// push `status = $n`, blackouts (blocked reservations without a user) are excluded unless
+/// include_blackouts is set, in which case they match whatever the status is
+fn push_status_cond(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    status: ReservationStatus,
+    include_blackouts: bool,
+) {
+    if include_blackouts {
+        builder
+            .push("(status = ")
+            .push_bind(status.to_string())
+            .push("::rsvp.reservation_status OR user_id IS NULL)");
+    } else {
+        builder
+            .push("status = ")
+            .push_bind(status.to_string())
+            .push("::rsvp.reservation_status");
+        if status == ReservationStatus::Blocked {
+            builder.push(" AND user_id IS NOT NULL");
+        }
+    }
+}
+
+/// push `AND user_id = $n AND resource_id = $m` for the non-empty ids, blackouts match any user id
+/// if include_blackouts is set
+fn push_user_resource_cond(
+    builder: &mut QueryBuilder<'static, Postgres>,
+    user_id: &str,
+    resource_id: &str,
+    include_blackouts: bool,
+) {
+    if !user_id.is_empty() {
+        if include_blackouts {
+            builder
+                .push(" AND (user_id = ")
+                .push_bind(user_id.to_string())
+                .push(" OR user_id IS NULL)");
+        } else {
+            builder
+                .push(" AND user_id = ")
+                .push_bind(user_id.to_string());
+        }
+    }
+    if !resource_id.is_empty() {
+        builder
+            .push(" AND resource_id = ")
+            .push_bind(resource_id.to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -84,4 +144,35 @@ mod tests {
         assert_eq!(range.start, Bound::Included(convert_to_utc_time(&start)));
         assert_eq!(range.end, Bound::Included(convert_to_utc_time(&end)));
     }
+
+    #[test]
+    fn push_status_cond_should_exclude_blackouts_unless_included() {
+        let mut builder = QueryBuilder::new("");
+        push_status_cond(&mut builder, ReservationStatus::Blocked, false);
+        assert_eq!(
+            builder.sql(),
+            "status = $1::rsvp.reservation_status AND user_id IS NOT NULL"
+        );
+
+        let mut builder = QueryBuilder::new("");
+        push_status_cond(&mut builder, ReservationStatus::Pending, true);
+        assert_eq!(
+            builder.sql(),
+            "(status = $1::rsvp.reservation_status OR user_id IS NULL)"
+        );
+    }
+
+    #[test]
+    fn push_user_resource_cond_should_match_blackouts_of_any_user() {
+        let mut builder = QueryBuilder::new("");
+        push_user_resource_cond(&mut builder, "tyr", "lab-1", true);
+        assert_eq!(
+            builder.sql(),
+            " AND (user_id = $1 OR user_id IS NULL) AND resource_id = $2"
+        );
+
+        let mut builder = QueryBuilder::new("");
+        push_user_resource_cond(&mut builder, "tyr", "", false);
+        assert_eq!(builder.sql(), " AND user_id = $1");
+    }
 }
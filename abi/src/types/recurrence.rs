@@ -0,0 +1,217 @@
+use crate::{convert_to_utc_time, Error, RecurrenceFrequency, RecurrenceRule, Validator};
+use chrono::{DateTime, Duration, Months, Utc};
+
+/// maximum number of occurrences a recurrence rule could expand to
+pub const MAX_OCCURRENCES: usize = 366;
+
+/// start and end of an occurrence
+type Occurrence = (DateTime<Utc>, DateTime<Utc>);
+
+impl Validator for RecurrenceRule {
+    fn validate(&self) -> Result<(), Error> {
+        match RecurrenceFrequency::from_i32(self.frequency) {
+            None | Some(RecurrenceFrequency::Unknown) => {
+                return Err(Error::InvalidRecurrence(format!(
+                    "unknown frequency {}",
+                    self.frequency
+                )));
+            }
+            _ => {}
+        }
+
+        if self.interval < 0 {
+            return Err(Error::InvalidRecurrence(format!(
+                "negative interval {}",
+                self.interval
+            )));
+        }
+
+        if self.count < 0 || self.count as usize > MAX_OCCURRENCES {
+            return Err(Error::InvalidRecurrence(format!(
+                "count should be between 0 and {}",
+                MAX_OCCURRENCES
+            )));
+        }
+
+        if self.count == 0 && self.until.is_none() {
+            return Err(Error::InvalidRecurrence(
+                "either count or until should be set".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl RecurrenceRule {
+    /// expand the rule into the (start, end) of every occurrence, starting with (start, end) itself.
+    /// the expansion is UTC only, occurrences keep the UTC time of day across DST changes
+    pub fn expand(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Occurrence>, Error> {
+        self.validate()?;
+
+        let duration = end - start;
+        let interval = self.interval.max(1) as u32;
+        let until = self.until.as_ref().map(convert_to_utc_time);
+        let exceptions: Vec<_> = self.exceptions.iter().map(convert_to_utc_time).collect();
+        let count = if self.count == 0 {
+            usize::MAX
+        } else {
+            self.count as usize
+        };
+
+        let mut occurrences = vec![];
+        for i in 0..count {
+            let occurrence = match (i as u32)
+                .checked_mul(interval)
+                .and_then(|n| self.nth(start, n))
+            {
+                Some(v) => v,
+                None => break,
+            };
+
+            if until.is_some_and(|until| occurrence > until) {
+                break;
+            }
+
+            if i >= MAX_OCCURRENCES {
+                return Err(Error::InvalidRecurrence(format!(
+                    "more than {} occurrences",
+                    MAX_OCCURRENCES
+                )));
+            }
+
+            if !exceptions.contains(&occurrence) {
+                occurrences.push((occurrence, occurrence + duration));
+            }
+        }
+
+        if occurrences.is_empty() {
+            return Err(Error::InvalidRecurrence(
+                "every occurrence is excluded".into(),
+            ));
+        }
+
+        Ok(occurrences)
+    }
+
+    // always step from the first occurrence, so a monthly rule starting on the 31st is clamped
+    // to the end of shorter months without drifting
+    fn nth(&self, start: DateTime<Utc>, n: u32) -> Option<DateTime<Utc>> {
+        match self.frequency() {
+            RecurrenceFrequency::Daily => start.checked_add_signed(Duration::days(n as i64)),
+            RecurrenceFrequency::Weekly => start.checked_add_signed(Duration::weeks(n as i64)),
+            RecurrenceFrequency::Monthly => start.checked_add_months(Months::new(n)),
+            RecurrenceFrequency::Unknown => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert_to_timestamp;
+
+    fn time(s: &str) -> DateTime<Utc> {
+        s.parse().unwrap()
+    }
+
+    fn rule(frequency: RecurrenceFrequency, interval: i32, count: i32) -> RecurrenceRule {
+        RecurrenceRule {
+            frequency: frequency as i32,
+            interval,
+            count,
+            until: None,
+            exceptions: vec![],
+        }
+    }
+
+    #[test]
+    fn weekly_rule_should_expand_by_count() {
+        let rule = rule(RecurrenceFrequency::Weekly, 1, 3);
+        let occurrences = rule
+            .expand(time("2023-12-04T09:00:00Z"), time("2023-12-04T09:30:00Z"))
+            .unwrap();
+
+        assert_eq!(
+            occurrences,
+            vec![
+                (time("2023-12-04T09:00:00Z"), time("2023-12-04T09:30:00Z")),
+                (time("2023-12-11T09:00:00Z"), time("2023-12-11T09:30:00Z")),
+                (time("2023-12-18T09:00:00Z"), time("2023-12-18T09:30:00Z")),
+            ]
+        );
+    }
+
+    #[test]
+    fn daily_rule_should_expand_until_and_skip_exceptions() {
+        let mut rule = rule(RecurrenceFrequency::Daily, 2, 0);
+        rule.until = Some(convert_to_timestamp(&time("2023-12-09T00:00:00Z")));
+        rule.exceptions = vec![convert_to_timestamp(&time("2023-12-05T09:00:00Z"))];
+        let occurrences = rule
+            .expand(time("2023-12-01T09:00:00Z"), time("2023-12-01T10:00:00Z"))
+            .unwrap();
+
+        let starts: Vec<_> = occurrences.into_iter().map(|(start, _)| start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                time("2023-12-01T09:00:00Z"),
+                time("2023-12-03T09:00:00Z"),
+                time("2023-12-07T09:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_rule_should_clamp_to_month_end() {
+        let rule = rule(RecurrenceFrequency::Monthly, 1, 3);
+        let occurrences = rule
+            .expand(time("2024-01-31T09:00:00Z"), time("2024-01-31T10:00:00Z"))
+            .unwrap();
+
+        let starts: Vec<_> = occurrences.into_iter().map(|(start, _)| start).collect();
+        assert_eq!(
+            starts,
+            vec![
+                time("2024-01-31T09:00:00Z"),
+                time("2024-02-29T09:00:00Z"),
+                time("2024-03-31T09:00:00Z"),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_rule_should_be_rejected() {
+        let start = time("2023-12-01T09:00:00Z");
+        let end = time("2023-12-01T10:00:00Z");
+
+        let unbounded = rule(RecurrenceFrequency::Daily, 1, 0);
+        assert!(unbounded.expand(start, end).is_err());
+
+        let unknown = rule(RecurrenceFrequency::Unknown, 1, 3);
+        assert!(unknown.expand(start, end).is_err());
+
+        let too_many = rule(RecurrenceFrequency::Daily, 1, MAX_OCCURRENCES as i32 + 1);
+        assert!(too_many.expand(start, end).is_err());
+
+        let mut too_long = rule(RecurrenceFrequency::Daily, 1, 0);
+        too_long.until = Some(convert_to_timestamp(&time("2030-01-01T00:00:00Z")));
+        assert!(too_long.expand(start, end).is_err());
+
+        let mut excluded = rule(RecurrenceFrequency::Daily, 1, 2);
+        excluded.exceptions = vec![
+            convert_to_timestamp(&start),
+            convert_to_timestamp(&time("2023-12-02T09:00:00Z")),
+        ];
+        assert_eq!(
+            excluded.expand(start, end),
+            Err(Error::InvalidRecurrence(
+                "every occurrence is excluded".into()
+            ))
+        );
+    }
+}
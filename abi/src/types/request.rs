@@ -1,7 +1,15 @@
 use crate::{
-    CancelRequest, ConfirmRequest, FilterRequest, GetRequest, QueryRequest, Reservation,
-    ReservationFilter, ReservationQuery, ReserveRequest, UpdateRequest,
+    AckRequest, BlackoutPolicy, BlackoutRequest, BookingRules, CancelRequest, CancelSeriesRequest,
+    ConfirmRequest, CreateResourceGroupRequest, CreateResourceRequest, DeactivateResourceRequest,
+    FilterRequest, GetBookingRulesRequest, GetRequest, GetResourceGroupRequest, GetResourceRequest,
+    JoinWaitlistRequest, LeaveWaitlistRequest, ListResourcesRequest, ListWaitlistRequest,
+    QueryRequest, RecurrenceRule, RescheduleRequest, Reservation, ReservationFilter,
+    ReservationQuery, ReservationStatus, ReserveAnyRequest, ReserveBatchRequest,
+    ReserveRecurringRequest, ReserveRequest, Resource, ResourceGroup, SelectionStrategy,
+    SetBookingRulesRequest, TransferRequest, TransitionRequest, UpdateRequest,
+    UpdateResourceGroupRequest, UpdateResourceRequest, UpdateSeriesRequest, WaitlistEntry,
 };
+use prost_types::Timestamp;
 
 macro_rules!impl_new {
     ($name:ident, $field:ident, $type:ty) => {
@@ -22,14 +30,199 @@ macro_rules!impl_new {
     };
 }
 
-impl_new!(ReserveRequest, reservation, Reservation);
 impl_new!(FilterRequest, filter, ReservationFilter);
 impl_new!(QueryRequest, query, ReservationQuery);
 impl_new!(ConfirmRequest, GetRequest);
-impl_new!(CancelRequest);
+impl_new!(CancelRequest, LeaveWaitlistRequest);
+impl_new!(CreateResourceRequest, resource, Resource);
+impl_new!(UpdateResourceRequest, resource, Resource);
+impl_new!(SetBookingRulesRequest, rules, BookingRules);
+impl_new!(CreateResourceGroupRequest, group, ResourceGroup);
+impl_new!(UpdateResourceGroupRequest, group, ResourceGroup);
+impl_new!(JoinWaitlistRequest, entry, WaitlistEntry);
+
+impl GetResourceRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl GetBookingRulesRequest {
+    pub fn new(resource_id: impl Into<String>) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+        }
+    }
+}
+
+impl GetResourceGroupRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl DeactivateResourceRequest {
+    pub fn new(id: impl Into<String>) -> Self {
+        Self { id: id.into() }
+    }
+}
+
+impl ListResourcesRequest {
+    /// list the resources of the type, all types if empty
+    pub fn new(resource_type: impl Into<String>, include_inactive: bool) -> Self {
+        Self {
+            r#type: resource_type.into(),
+            include_inactive,
+        }
+    }
+}
 
 impl UpdateRequest {
     pub fn new(id: i64, note: String) -> Self {
         Self { id, note }
     }
 }
+
+impl ReserveRequest {
+    pub fn new(reservation: Reservation) -> Self {
+        Self {
+            reservation: Some(reservation),
+            ..Default::default()
+        }
+    }
+
+    /// hold the pending reservation for hold_ttl seconds before it expires
+    pub fn new_with_hold(reservation: Reservation, hold_ttl: i64) -> Self {
+        Self {
+            reservation: Some(reservation),
+            hold_ttl,
+            ..Default::default()
+        }
+    }
+
+    /// suggest up to count nearest free windows on the resource and its siblings on conflict
+    pub fn new_with_suggestions(
+        reservation: Reservation,
+        count: i32,
+        sibling_resource_ids: Vec<String>,
+    ) -> Self {
+        Self {
+            reservation: Some(reservation),
+            suggestions: count,
+            sibling_resource_ids,
+            ..Default::default()
+        }
+    }
+}
+
+impl TransitionRequest {
+    pub fn new(id: i64, status: ReservationStatus) -> Self {
+        Self {
+            id,
+            status: status as i32,
+        }
+    }
+}
+
+impl BlackoutRequest {
+    pub fn new(blackout: Reservation, policy: BlackoutPolicy) -> Self {
+        Self {
+            resource_id: blackout.resource_id,
+            start: blackout.start,
+            end: blackout.end,
+            note: blackout.note,
+            policy: policy as i32,
+        }
+    }
+}
+
+impl ReserveBatchRequest {
+    pub fn new(reservations: Vec<Reservation>) -> Self {
+        Self { reservations }
+    }
+}
+
+impl RescheduleRequest {
+    pub fn new(id: i64, start: Timestamp, end: Timestamp) -> Self {
+        Self {
+            id,
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+}
+
+impl TransferRequest {
+    pub fn new(id: i64, resource_id: impl Into<String>) -> Self {
+        Self {
+            id,
+            resource_id: resource_id.into(),
+        }
+    }
+}
+
+impl ReserveRecurringRequest {
+    pub fn new(reservation: Reservation, rule: RecurrenceRule) -> Self {
+        Self {
+            reservation: Some(reservation),
+            rule: Some(rule),
+        }
+    }
+}
+
+impl ReserveAnyRequest {
+    pub fn new(
+        reservation: Reservation,
+        group_id: impl Into<String>,
+        strategy: SelectionStrategy,
+    ) -> Self {
+        Self {
+            reservation: Some(reservation),
+            group_id: group_id.into(),
+            strategy: strategy as i32,
+        }
+    }
+}
+
+impl ListWaitlistRequest {
+    pub fn new(resource_id: impl Into<String>, start: Timestamp, end: Timestamp) -> Self {
+        Self {
+            resource_id: resource_id.into(),
+            start: Some(start),
+            end: Some(end),
+        }
+    }
+}
+
+impl UpdateSeriesRequest {
+    pub fn new(series_id: i64, note: impl Into<String>) -> Self {
+        Self {
+            series_id,
+            note: Some(note.into()),
+            shift: 0,
+        }
+    }
+
+    pub fn shift(series_id: i64, shift: i64) -> Self {
+        Self {
+            series_id,
+            note: None,
+            shift,
+        }
+    }
+}
+
+impl CancelSeriesRequest {
+    pub fn new(series_id: i64) -> Self {
+        Self { series_id }
+    }
+}
+
+impl AckRequest {
+    pub fn new(consumer_id: impl Into<String>, change_id: i64) -> Self {
+        Self {
+            consumer_id: consumer_id.into(),
+            change_id,
+        }
+    }
+}
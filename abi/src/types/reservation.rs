@@ -1,7 +1,9 @@
 use crate::{
-    utils::convert_to_timestamp, Error, Reservation, ReservationStatus, RsvpStatus, Validator,
+    utils::{convert_to_timestamp, convert_to_utc_time},
+    Error, Reservation, ReservationStatus, RsvpStatus, Validator,
 };
-use chrono::{DateTime, FixedOffset, Utc};
+use chrono::{DateTime, Duration, FixedOffset, Utc};
+use serde::Deserialize;
 use sqlx::{
     postgres::{types::PgRange, PgRow},
     FromRow, Row,
@@ -26,12 +28,53 @@ impl Reservation {
             end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
             note: note.into(),
             status: ReservationStatus::Pending as i32,
+            series_id: 0,
+            hold_expires_at: None,
+            flagged: false,
+            blocked_start: None,
+            blocked_end: None,
         }
     }
 
+    /// blackouts take a resource offline for all users, they are blocked reservations without a user
+    pub fn new_blackout(
+        rid: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            status: ReservationStatus::Blocked as i32,
+            ..Self::new_pending("", rid, start, end, note)
+        }
+    }
+
+    pub fn is_blackout(&self) -> bool {
+        self.user_id.is_empty() && self.status == ReservationStatus::Blocked as i32
+    }
+
+    /// hold the pending reservation for ttl seconds from now, it expires if not confirmed by then
+    pub fn hold_for(&mut self, ttl: i64) -> Result<(), Error> {
+        if ttl <= 0 || ttl > i64::MAX / 1000 {
+            return Err(Error::InvalidHoldTtl(ttl));
+        }
+        let expires_at = Utc::now()
+            .checked_add_signed(Duration::seconds(ttl))
+            .ok_or(Error::InvalidHoldTtl(ttl))?;
+        self.hold_expires_at = Some(convert_to_timestamp(&expires_at));
+        Ok(())
+    }
+
     pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
         get_timespan(self.start.as_ref(), self.end.as_ref())
     }
+
+    /// set blocked_start and blocked_end from the padded timespan of the row
+    pub fn set_blocked_timespan(&mut self, range: PgRange<DateTime<Utc>>) {
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+        self.blocked_start = range.start.map(|v| convert_to_timestamp(&v));
+        self.blocked_end = range.end.map(|v| convert_to_timestamp(&v));
+    }
 }
 
 impl Validator for Reservation {
@@ -46,6 +89,21 @@ impl Validator for Reservation {
 
         validate_range(self.start.as_ref(), self.end.as_ref())?;
 
+        // a new reservation is pending, any other status is reached through a transition
+        if !matches!(
+            ReservationStatus::from_i32(self.status),
+            Some(ReservationStatus::Unknown | ReservationStatus::Pending)
+        ) {
+            return Err(Error::InvalidStatus(self.status));
+        }
+
+        // a hold expiring already would be reaped right away
+        if let Some(expires_at) = self.hold_expires_at.as_ref() {
+            if convert_to_utc_time(expires_at) <= Utc::now() {
+                return Err(Error::InvalidHoldExpiry(expires_at.seconds));
+            }
+        }
+
         Ok(())
     }
 }
@@ -65,21 +123,71 @@ impl FromRow<'_, PgRow> for Reservation {
 
         let status: RsvpStatus = row.get("status");
 
-        Ok(Self {
+        let mut rsvp = Self {
             id,
-            user_id: row.get("user_id"),
+            user_id: row.get::<Option<String>, _>("user_id").unwrap_or_default(),
             resource_id: row.get("resource_id"),
             start: Some(convert_to_timestamp(&start)),
             end: Some(convert_to_timestamp(&end)),
             note: row.get("note"),
             status: ReservationStatus::from(status) as i32,
-        })
+            series_id: row.get::<Option<i64>, _>("series_id").unwrap_or_default(),
+            hold_expires_at: row
+                .get::<Option<DateTime<Utc>>, _>("hold_expires_at")
+                .map(|v| convert_to_timestamp(&v)),
+            flagged: row.get("flagged"),
+            blocked_start: None,
+            blocked_end: None,
+        };
+        rsvp.set_blocked_timespan(row.get("padded_timespan"));
+        Ok(rsvp)
     }
 }
 
-struct NaiveRange<T> {
-    start: Option<T>,
-    end: Option<T>,
+/// reservation row as encoded by to_jsonb() in rsvp.reservations_trigger(). the ranges are text
+/// in the format of the writing session, their bounds are read with lower() and upper() instead
+#[derive(Debug, Deserialize)]
+pub(crate) struct ReservationJson {
+    id: i64,
+    user_id: Option<String>,
+    status: RsvpStatus,
+    resource_id: String,
+    note: Option<String>,
+    #[serde(default)]
+    series_id: Option<i64>,
+    #[serde(default)]
+    hold_expires_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    flagged: bool,
+}
+
+impl ReservationJson {
+    /// timespan is required, blocked is missing for changes recorded before the buffers
+    pub(crate) fn into_reservation(
+        self,
+        timespan: (DateTime<Utc>, DateTime<Utc>),
+        blocked: Option<(DateTime<Utc>, DateTime<Utc>)>,
+    ) -> Reservation {
+        Reservation {
+            id: self.id,
+            user_id: self.user_id.unwrap_or_default(),
+            resource_id: self.resource_id,
+            start: Some(convert_to_timestamp(&timespan.0)),
+            end: Some(convert_to_timestamp(&timespan.1)),
+            note: self.note.unwrap_or_default(),
+            status: ReservationStatus::from(self.status) as i32,
+            series_id: self.series_id.unwrap_or_default(),
+            hold_expires_at: self.hold_expires_at.map(|v| convert_to_timestamp(&v)),
+            flagged: self.flagged,
+            blocked_start: blocked.map(|(start, _)| convert_to_timestamp(&start)),
+            blocked_end: blocked.map(|(_, end)| convert_to_timestamp(&end)),
+        }
+    }
+}
+
+pub(super) struct NaiveRange<T> {
+    pub(super) start: Option<T>,
+    pub(super) end: Option<T>,
 }
 
 impl<T> From<PgRange<T>> for NaiveRange<T> {
@@ -96,3 +204,72 @@ impl<T> From<PgRange<T>> for NaiveRange<T> {
         Self { start, end }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hold_for_should_reject_invalid_ttl() {
+        let mut rsvp = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "hold",
+        );
+
+        assert_eq!(rsvp.hold_for(0), Err(Error::InvalidHoldTtl(0)));
+        assert_eq!(rsvp.hold_for(-1), Err(Error::InvalidHoldTtl(-1)));
+        assert_eq!(
+            rsvp.hold_for(i64::MAX),
+            Err(Error::InvalidHoldTtl(i64::MAX))
+        );
+        assert!(rsvp.hold_expires_at.is_none());
+
+        rsvp.hold_for(60).unwrap();
+        assert!(rsvp.hold_expires_at.is_some());
+    }
+
+    #[test]
+    fn validate_should_reject_hold_expired_already() {
+        let mut rsvp = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "hold",
+        );
+        let past = convert_to_timestamp(&(Utc::now() - Duration::seconds(1)));
+        rsvp.hold_expires_at = Some(past.clone());
+        assert_eq!(rsvp.validate(), Err(Error::InvalidHoldExpiry(past.seconds)));
+
+        rsvp.hold_for(60).unwrap();
+        assert!(rsvp.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_should_reject_status_other_than_pending() {
+        let mut rsvp = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "hold",
+        );
+        for status in [
+            ReservationStatus::Confirmed,
+            ReservationStatus::Blocked,
+            ReservationStatus::Cancelled,
+        ] {
+            rsvp.status = status as i32;
+            assert_eq!(rsvp.validate(), Err(Error::InvalidStatus(status as i32)));
+        }
+        rsvp.status = 42;
+        assert_eq!(rsvp.validate(), Err(Error::InvalidStatus(42)));
+
+        // an unset status is pending
+        rsvp.status = ReservationStatus::Unknown as i32;
+        assert!(rsvp.validate().is_ok());
+    }
+}
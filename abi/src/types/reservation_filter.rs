@@ -1,10 +1,12 @@
 use std::collections::VecDeque;
 
+use super::{push_status_cond, push_user_resource_cond};
 use crate::{
     pager::{Id, PageInfo, Pager, Paginator},
     Error, FilterPager, Normalizer, Reservation, ReservationFilter, ReservationFilterBuilder,
     ReservationStatus, ToSql, Validator,
 };
+use sqlx::{Postgres, QueryBuilder};
 
 impl ReservationFilterBuilder {
     pub fn build(&self) -> Result<ReservationFilter, Error> {
@@ -67,6 +69,7 @@ impl ReservationFilter {
             status: self.status,
             user_id: self.user_id.clone(),
             resource_id: self.resource_id.clone(),
+            include_blackouts: self.include_blackouts,
         })
     }
 
@@ -106,55 +109,33 @@ impl Id for Reservation {
 }
 
 impl ToSql for ReservationFilter {
-    fn to_sql(&self) -> String {
+    fn to_sql(&self) -> QueryBuilder<'static, Postgres> {
         let middle_plus = if self.cursor.is_none() { 0 } else { 1 };
         let limit = self.page_size + 1 + middle_plus;
 
         let status = self.get_status();
-
-        let cursor_cond = if self.desc {
-            format!("id <= {}", self.get_cursor())
-        } else {
-            format!("id >= {}", self.get_cursor())
-        };
-
-        let user_resource_cond = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
-        };
+        let cursor_op = if self.desc { "<=" } else { ">=" };
+
+        let mut builder = QueryBuilder::new("SELECT * FROM rsvp.reservations WHERE ");
+        push_status_cond(&mut builder, status, self.include_blackouts);
+        builder
+            .push(" AND id ")
+            .push(cursor_op)
+            .push(" ")
+            .push_bind(self.get_cursor());
+
+        push_user_resource_cond(
+            &mut builder,
+            &self.user_id,
+            &self.resource_id,
+            self.include_blackouts,
+        );
 
         let direction = if self.desc { "DESC" } else { "ASC" };
-
-        // if self.desc {
-        //     sql.push_str(&format!("id <= {} ", self.get_cursor()));
-        // } else {
-        //     sql.push_str(&format!("id >= {} ", self.get_cursor()));
-        // }
-        // if self.user_id.is_empty() && self.resource_id.is_empty() {
-        //     sql.push_str("TRUE");
-        // } else if self.user_id.is_empty() {
-        //     sql.push_str(&format!("resource_id = {} ", self.resource_id));
-        // } else if self.resource_id.is_empty() {
-        //     sql.push_str(&format!("user_id = {} ", self.user_id));
-        // } else {
-        //     sql.push_str(&format!(
-        //         "user_id = '{}' AND resource_id = '{}'",
-        //         self.user_id, self.resource_id
-        //     ));
-        // }
-        // sql.push_str(&format!(
-        //     "OREDER BY id {} LIMIT {}",
-        //     if self.desc { "DESC" } else { "ASC" },
-        //     self.page_size + 1 + middle_plus
-        // ));
-        // sql
-
-        format!("SELECT * FROM rsvp.reservations WHERE status = '{}'::rsvp.reservation_status AND {} AND {} ORDER BY id {} LIMIT {}", status, cursor_cond, user_resource_cond, direction, limit)
+        builder
+            .push(format!(" ORDER BY id {} LIMIT ", direction))
+            .push_bind(limit);
+        builder
     }
 }
 
@@ -172,8 +153,8 @@ mod tests {
 
         let sql = filter.to_sql();
         assert_eq!(
-            sql,
-            "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND user_id = 'tyr' ORDER BY id ASC LIMIT 11"
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id >= $2 AND user_id = $3 ORDER BY id ASC LIMIT $4"
         );
 
         let filter: ReservationFilter = ReservationFilterBuilder::default()
@@ -185,8 +166,8 @@ mod tests {
         let sql = filter.to_sql();
 
         assert_eq!(
-            sql,
-            "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 0 AND user_id = 'tyr' AND resource_id = 'test' ORDER BY id ASC LIMIT 11"
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id >= $2 AND user_id = $3 AND resource_id = $4 ORDER BY id ASC LIMIT $5"
         );
 
         let filter = ReservationFilterBuilder::default()
@@ -196,8 +177,8 @@ mod tests {
 
         let sql = filter.to_sql();
         assert_eq!(
-            sql,
-            "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id <= 9223372036854775807 AND TRUE ORDER BY id DESC LIMIT 11"
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id <= $2 ORDER BY id DESC LIMIT $3"
         );
 
         let filter = ReservationFilterBuilder::default()
@@ -207,8 +188,8 @@ mod tests {
             .unwrap();
         let sql = filter.to_sql();
         assert_eq!(
-            sql,
-            "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 100 AND user_id = 'tyr' ORDER BY id ASC LIMIT 12"
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id >= $2 AND user_id = $3 ORDER BY id ASC LIMIT $4"
         );
 
         let filter = ReservationFilterBuilder::default()
@@ -220,8 +201,23 @@ mod tests {
 
         let sql = filter.to_sql();
         assert_eq!(
-            sql,
-            "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id <= 10 AND user_id = 'tyr' ORDER BY id DESC LIMIT 12"
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id <= $2 AND user_id = $3 ORDER BY id DESC LIMIT $4"
+        );
+    }
+
+    #[test]
+    fn filter_should_include_blackouts() {
+        let filter = ReservationFilterBuilder::default()
+            .user_id("tyr")
+            .include_blackouts(true)
+            .build()
+            .unwrap();
+
+        let sql = filter.to_sql();
+        assert_eq!(
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE (status = $1::rsvp.reservation_status OR user_id IS NULL) AND id >= $2 AND (user_id = $3 OR user_id IS NULL) ORDER BY id ASC LIMIT $4"
         );
     }
 
@@ -239,11 +235,26 @@ mod tests {
         let filter = filter.next_page(&pager).unwrap();
         let sql = filter.to_sql();
 
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE status = 'pending'::rsvp.reservation_status AND id >= 10 AND resource_id = 'route-1' ORDER BY id ASC LIMIT 12");
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id >= $2 AND resource_id = $3 ORDER BY id ASC LIMIT $4");
 
         let mut data = generate_test_ids(10, 21);
         let pager = filter.get_pager(&mut data);
         assert_eq!(pager.prev, Some(11));
         assert_eq!(pager.next, Some(20));
     }
+
+    #[test]
+    fn filter_should_bind_hostile_ids() {
+        let filter = ReservationFilterBuilder::default()
+            .user_id("tyr' OR '1'='1")
+            .resource_id("room'; DROP TABLE rsvp.reservations; --")
+            .build()
+            .unwrap();
+
+        let sql = filter.to_sql();
+        assert_eq!(
+            sql.sql(),
+            "SELECT * FROM rsvp.reservations WHERE status = $1::rsvp.reservation_status AND id >= $2 AND user_id = $3 AND resource_id = $4 ORDER BY id ASC LIMIT $5"
+        );
+    }
 }
@@ -1,8 +1,9 @@
+use super::{push_status_cond, push_user_resource_cond};
 use crate::{
     convert_to_utc_time, Error, Normalizer, ReservationQuery, ReservationQueryBuilder,
     ReservationStatus, ToSql, Validator,
 };
-use prost_types::Timestamp;
+use sqlx::{Postgres, QueryBuilder};
 
 impl ReservationQueryBuilder {
     pub fn build(&self) -> Result<ReservationQuery, Error> {
@@ -69,35 +70,28 @@ impl Normalizer for ReservationQuery {
 }
 
 impl ToSql for ReservationQuery {
-    fn to_sql(&self) -> String {
+    fn to_sql(&self) -> QueryBuilder<'static, Postgres> {
         let status = self.get_status();
 
-        let timespan = format!(
-            "tstzrange('{}', '{}')",
-            get_time_string(self.start.as_ref(), true),
-            get_time_string(self.end.as_ref(), false)
+        // a missing start or end leaves the range unbounded on that side
+        let mut builder = QueryBuilder::new("SELECT * FROM rsvp.reservations WHERE tstzrange(");
+        builder
+            .push_bind(self.start.as_ref().map(convert_to_utc_time))
+            .push(", ")
+            .push_bind(self.end.as_ref().map(convert_to_utc_time))
+            .push(") @> timespan AND ");
+
+        push_status_cond(&mut builder, status, self.include_blackouts);
+        push_user_resource_cond(
+            &mut builder,
+            &self.user_id,
+            &self.resource_id,
+            self.include_blackouts,
         );
 
-        let condition = match (self.user_id.is_empty(), self.resource_id.is_empty()) {
-            (true, true) => "TRUE".into(),
-            (true, false) => format!("resource_id = '{}'", self.resource_id),
-            (false, true) => format!("user_id = '{}'", self.user_id),
-            (false, false) => format!(
-                "user_id = '{}' AND resource_id = '{}'",
-                self.user_id, self.resource_id
-            ),
-        };
-
         let direction = if self.desc { "DESC" } else { "ASC" };
-
-        format!("SELECT * FROM rsvp.reservations WHERE {} @> timespan AND status = '{}'::rsvp.reservation_status AND {} ORDER BY lower(timespan) {}", timespan, status, condition, direction)
-    }
-}
-
-fn get_time_string(ts: Option<&Timestamp>, start: bool) -> String {
-    match ts {
-        Some(ts) => convert_to_utc_time(ts).to_rfc3339(),
-        None => (if start { "-infinity" } else { "infinity" }).into(),
+        builder.push(format!(" ORDER BY lower(timespan) {}", direction));
+        builder
     }
 }
 
@@ -105,6 +99,8 @@ fn get_time_string(ts: Option<&Timestamp>, start: bool) -> String {
 mod tests {
     use super::*;
 
+    use prost_types::Timestamp;
+
     #[test]
     fn query_should_generate_valid_sql() {
         let query = ReservationQueryBuilder::default()
@@ -114,7 +110,7 @@ mod tests {
 
         let sql = query.to_sql();
 
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND user_id = 'tyr' ORDER BY lower(timespan) ASC");
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND status = $3::rsvp.reservation_status AND user_id = $4 ORDER BY lower(timespan) ASC");
 
         let query = ReservationQueryBuilder::default()
             .resource_id("test")
@@ -123,14 +119,48 @@ mod tests {
             .unwrap();
 
         let sql = query.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('2021-11-01T22:00:00+00:00', 'infinity') @> timespan AND status = 'pending'::rsvp.reservation_status AND resource_id = 'test' ORDER BY lower(timespan) ASC");
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND status = $3::rsvp.reservation_status AND resource_id = $4 ORDER BY lower(timespan) ASC");
 
         let query = ReservationQueryBuilder::default()
             .end("2021-11-01T16:00:00-0700".parse::<Timestamp>().unwrap())
+            .desc(true)
+            .build()
+            .unwrap();
+
+        let sql = query.to_sql();
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND status = $3::rsvp.reservation_status ORDER BY lower(timespan) DESC");
+    }
+
+    #[test]
+    fn query_should_bind_hostile_ids() {
+        let query = ReservationQueryBuilder::default()
+            .user_id("tyr' OR '1'='1")
+            .resource_id("room'; DROP TABLE rsvp.reservations; --")
+            .build()
+            .unwrap();
+
+        let sql = query.to_sql();
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND status = $3::rsvp.reservation_status AND user_id = $4 AND resource_id = $5 ORDER BY lower(timespan) ASC");
+    }
+
+    #[test]
+    fn query_should_include_or_exclude_blackouts() {
+        let query = ReservationQueryBuilder::default()
+            .user_id("tyr")
+            .resource_id("lab-1")
+            .include_blackouts(true)
+            .build()
+            .unwrap();
+
+        let sql = query.to_sql();
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND (status = $3::rsvp.reservation_status OR user_id IS NULL) AND (user_id = $4 OR user_id IS NULL) AND resource_id = $5 ORDER BY lower(timespan) ASC");
+
+        let query = ReservationQueryBuilder::default()
+            .status(ReservationStatus::Blocked as i32)
             .build()
             .unwrap();
 
         let sql = query.to_sql();
-        assert_eq!(sql, "SELECT * FROM rsvp.reservations WHERE tstzrange('-infinity', '2021-11-01T23:00:00+00:00') @> timespan AND status = 'pending'::rsvp.reservation_status AND TRUE ORDER BY lower(timespan) ASC");
+        assert_eq!(sql.sql(), "SELECT * FROM rsvp.reservations WHERE tstzrange($1, $2) @> timespan AND status = $3::rsvp.reservation_status AND user_id IS NOT NULL ORDER BY lower(timespan) ASC");
     }
 }
@@ -7,18 +7,74 @@ impl From<RsvpStatus> for ReservationStatus {
             RsvpStatus::Pending => ReservationStatus::Pending,
             RsvpStatus::Blocked => ReservationStatus::Blocked,
             RsvpStatus::Confirmed => ReservationStatus::Confirmed,
+            RsvpStatus::Cancelled => ReservationStatus::Cancelled,
             RsvpStatus::Unknown => ReservationStatus::Unknown,
         }
     }
 }
 
+impl ReservationStatus {
+    /// reservation status state machine:
+    ///
+    /// - pending -> confirmed: the owner confirms the reservation
+    /// - pending/confirmed -> blocked: the resource is taken for maintenance
+    /// - blocked -> pending: the reservation is released back to its owner, who should confirm it again
+    /// - pending/confirmed/blocked -> cancelled: the reservation is cancelled, it is kept for history
+    ///
+    /// staying in the same status is always allowed, nothing could transition to unknown or out of
+    /// cancelled
+    pub fn can_transition_to(&self, to: ReservationStatus) -> bool {
+        matches!(
+            (self, to),
+            (ReservationStatus::Pending, ReservationStatus::Pending)
+                | (ReservationStatus::Pending, ReservationStatus::Confirmed)
+                | (ReservationStatus::Pending, ReservationStatus::Blocked)
+                | (ReservationStatus::Confirmed, ReservationStatus::Confirmed)
+                | (ReservationStatus::Confirmed, ReservationStatus::Blocked)
+                | (ReservationStatus::Blocked, ReservationStatus::Blocked)
+                | (ReservationStatus::Blocked, ReservationStatus::Pending)
+                | (ReservationStatus::Pending, ReservationStatus::Cancelled)
+                | (ReservationStatus::Confirmed, ReservationStatus::Cancelled)
+                | (ReservationStatus::Blocked, ReservationStatus::Cancelled)
+                | (ReservationStatus::Cancelled, ReservationStatus::Cancelled)
+        )
+    }
+}
+
 impl fmt::Display for ReservationStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             ReservationStatus::Pending => write!(f, "pending"),
             ReservationStatus::Blocked => write!(f, "blocked"),
             ReservationStatus::Confirmed => write!(f, "confirmed"),
+            ReservationStatus::Cancelled => write!(f, "cancelled"),
             ReservationStatus::Unknown => write!(f, "unknown"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_transitions_should_follow_state_machine() {
+        use ReservationStatus::*;
+
+        assert!(Pending.can_transition_to(Confirmed));
+        assert!(Pending.can_transition_to(Blocked));
+        assert!(Confirmed.can_transition_to(Blocked));
+        assert!(Blocked.can_transition_to(Pending));
+        assert!(Confirmed.can_transition_to(Confirmed));
+
+        assert!(!Confirmed.can_transition_to(Pending));
+        assert!(!Blocked.can_transition_to(Confirmed));
+        assert!(!Pending.can_transition_to(Unknown));
+        assert!(!Unknown.can_transition_to(Pending));
+
+        assert!(Confirmed.can_transition_to(Cancelled));
+        assert!(Blocked.can_transition_to(Cancelled));
+        assert!(!Cancelled.can_transition_to(Pending));
+        assert!(!Cancelled.can_transition_to(Confirmed));
+    }
+}
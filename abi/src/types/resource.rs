@@ -0,0 +1,198 @@
+use crate::{Error, Normalizer, Resource, Validator};
+use sqlx::{postgres::PgRow, types::JsonValue, FromRow, Row};
+
+/// same limit as the VARCHAR(64) resource_id of the reservations
+pub(crate) const MAX_RESOURCE_ID_LEN: usize = 64;
+
+impl Resource {
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_type: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            r#type: resource_type.into(),
+            attributes: "{}".into(),
+            active: Some(true),
+            capacity: 1,
+            buffer_before: 0,
+            buffer_after: 0,
+            parent_id: String::new(),
+        }
+    }
+
+    /// a part of another resource, e.g. a bench of a lab
+    pub fn new_child(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_type: impl Into<String>,
+        parent_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            parent_id: parent_id.into(),
+            ..Self::new(id, name, resource_type)
+        }
+    }
+
+    /// parent id to store, None for a top level resource
+    pub fn get_parent_id(&self) -> Option<&str> {
+        Some(self.parent_id.as_str()).filter(|v| !v.is_empty())
+    }
+
+    /// a pool of interchangeable units, up to capacity reservations may overlap
+    pub fn new_pool(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_type: impl Into<String>,
+        capacity: i32,
+    ) -> Self {
+        Self {
+            capacity,
+            ..Self::new(id, name, resource_type)
+        }
+    }
+
+    /// attributes as a JSON object, call validate() first
+    pub fn get_attributes(&self) -> JsonValue {
+        self.attributes
+            .parse()
+            .unwrap_or_else(|_| JsonValue::Object(Default::default()))
+    }
+}
+
+impl Validator for Resource {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() || self.id.chars().count() > MAX_RESOURCE_ID_LEN {
+            return Err(Error::InvalidResourceId(self.id.clone()));
+        }
+
+        if self.parent_id == self.id || self.parent_id.chars().count() > MAX_RESOURCE_ID_LEN {
+            return Err(Error::InvalidResourceParent(self.parent_id.clone()));
+        }
+
+        if self.capacity < 0 {
+            return Err(Error::InvalidCapacity(self.capacity));
+        }
+
+        for buffer in [self.buffer_before, self.buffer_after] {
+            if buffer < 0 {
+                return Err(Error::InvalidDuration(buffer));
+            }
+        }
+
+        if !self.attributes.is_empty() {
+            match self.attributes.parse::<JsonValue>() {
+                Ok(JsonValue::Object(_)) => {}
+                Ok(_) => return Err(Error::InvalidResourceAttributes("not a JSON object".into())),
+                Err(e) => return Err(Error::InvalidResourceAttributes(e.to_string())),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Normalizer for Resource {
+    // same compact form as read back from the jsonb column
+    fn do_normalize(&mut self) {
+        self.attributes = self.get_attributes().to_string();
+        self.capacity = self.capacity.max(1);
+    }
+}
+
+impl FromRow<'_, PgRow> for Resource {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let attributes: JsonValue = row.get("attributes");
+        Ok(Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            r#type: row.get("type"),
+            attributes: attributes.to_string(),
+            active: Some(row.get("active")),
+            capacity: row.get("capacity"),
+            buffer_before: row.get("buffer_before"),
+            buffer_after: row.get("buffer_after"),
+            parent_id: row
+                .get::<Option<String>, _>("parent_id")
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_should_validate_id_and_attributes() {
+        let mut resource = Resource::new("ixia-3230", "Ixia 3230", "device");
+        resource.attributes = r#"{"ports": 8}"#.into();
+        assert!(resource.validate().is_ok());
+
+        resource.attributes = "[1, 2]".into();
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceAttributes("not a JSON object".into())
+        );
+
+        resource.attributes = "{".into();
+        assert!(matches!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceAttributes(_)
+        ));
+
+        let resource = Resource::new("", "nameless", "device");
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceId("".into())
+        );
+        let resource = Resource::new("r".repeat(65), "too long", "device");
+        assert!(matches!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceId(_)
+        ));
+
+        let resource = Resource::new_pool("desks", "Desks", "desk", -1);
+        assert_eq!(resource.validate().unwrap_err(), Error::InvalidCapacity(-1));
+        let mut resource = Resource::new_pool("desks", "Desks", "desk", 0);
+        resource.normalize().unwrap();
+        assert_eq!(resource.capacity, 1);
+
+        let resource = Resource {
+            buffer_after: -60,
+            ..Resource::new("lab-1", "Lab 1", "lab")
+        };
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidDuration(-60)
+        );
+
+        let resource = Resource::new_child("lab-1", "Lab 1", "lab", "lab-1");
+        assert_eq!(
+            resource.validate().unwrap_err(),
+            Error::InvalidResourceParent("lab-1".into())
+        );
+        let resource = Resource::new_child("bench-1", "Bench 1", "bench", "lab-1");
+        assert!(resource.validate().is_ok());
+        assert_eq!(resource.get_parent_id(), Some("lab-1"));
+        assert_eq!(Resource::new("lab-1", "Lab 1", "lab").get_parent_id(), None);
+    }
+
+    #[test]
+    fn resource_attributes_should_normalize_to_compact_object() {
+        let mut resource = Resource::new("ixia-3230", "Ixia 3230", "device");
+        resource.attributes = "".into();
+        resource.normalize().unwrap();
+        assert_eq!(resource.attributes, "{}");
+        assert_eq!(
+            resource.get_attributes(),
+            JsonValue::Object(Default::default())
+        );
+
+        resource.attributes = r#"{ "ports": 8 }"#.into();
+        resource.normalize().unwrap();
+        assert_eq!(resource.attributes, r#"{"ports":8}"#);
+    }
+}
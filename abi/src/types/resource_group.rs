@@ -0,0 +1,84 @@
+use crate::{Error, ResourceGroup, Validator};
+use sqlx::{postgres::PgRow, FromRow, Row};
+use std::collections::HashSet;
+
+use super::resource::MAX_RESOURCE_ID_LEN;
+
+impl ResourceGroup {
+    pub fn new(
+        id: impl Into<String>,
+        name: impl Into<String>,
+        resource_ids: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            resource_ids: resource_ids.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Validator for ResourceGroup {
+    fn validate(&self) -> Result<(), Error> {
+        if self.id.is_empty() || self.id.chars().count() > MAX_RESOURCE_ID_LEN {
+            return Err(Error::InvalidResourceGroup(format!(
+                "invalid id {}",
+                self.id
+            )));
+        }
+
+        let mut seen = HashSet::new();
+        for rid in &self.resource_ids {
+            if rid.is_empty() {
+                return Err(Error::InvalidResourceId(rid.clone()));
+            }
+            if !seen.insert(rid) {
+                return Err(Error::InvalidResourceGroup(format!(
+                    "duplicate member {}",
+                    rid
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// row of the groups joined with their members aggregated in order as resource_ids
+impl FromRow<'_, PgRow> for ResourceGroup {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        Ok(Self {
+            id: row.get("id"),
+            name: row.get("name"),
+            resource_ids: row.get("resource_ids"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resource_group_should_reject_invalid_members() {
+        let group = ResourceGroup::new("ixias", "Ixia testers", ["ixia-1", "ixia-2"]);
+        assert!(group.validate().is_ok());
+
+        let group = ResourceGroup::new("", "nameless", ["ixia-1"]);
+        assert!(matches!(
+            group.validate(),
+            Err(Error::InvalidResourceGroup(_))
+        ));
+
+        let group = ResourceGroup::new("ixias", "Ixia testers", ["ixia-1", ""]);
+        assert_eq!(group.validate(), Err(Error::InvalidResourceId("".into())));
+
+        let group = ResourceGroup::new("ixias", "Ixia testers", ["ixia-1", "ixia-1"]);
+        assert_eq!(
+            group.validate(),
+            Err(Error::InvalidResourceGroup(
+                "duplicate member ixia-1".into()
+            ))
+        );
+    }
+}
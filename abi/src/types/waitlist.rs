@@ -0,0 +1,121 @@
+use crate::{
+    utils::convert_to_timestamp, Error, Reservation, ReservationStatus, Validator, WaitlistEntry,
+};
+use chrono::{DateTime, FixedOffset, Utc};
+use sqlx::{
+    postgres::{types::PgRange, PgRow},
+    FromRow, Row,
+};
+
+use super::{get_timespan, reservation::NaiveRange, validate_range};
+
+impl WaitlistEntry {
+    pub fn new(
+        uid: impl Into<String>,
+        rid: impl Into<String>,
+        start: DateTime<FixedOffset>,
+        end: DateTime<FixedOffset>,
+        note: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: 0,
+            user_id: uid.into(),
+            resource_id: rid.into(),
+            start: Some(convert_to_timestamp(&start.with_timezone(&Utc))),
+            end: Some(convert_to_timestamp(&end.with_timezone(&Utc))),
+            note: note.into(),
+        }
+    }
+
+    pub fn get_timespan(&self) -> PgRange<DateTime<Utc>> {
+        get_timespan(self.start.as_ref(), self.end.as_ref())
+    }
+
+    /// the pending reservation the entry is promoted to
+    pub fn to_reservation(&self) -> Reservation {
+        Reservation {
+            user_id: self.user_id.clone(),
+            resource_id: self.resource_id.clone(),
+            start: self.start.clone(),
+            end: self.end.clone(),
+            note: self.note.clone(),
+            status: ReservationStatus::Pending as i32,
+            ..Default::default()
+        }
+    }
+}
+
+impl Validator for WaitlistEntry {
+    fn validate(&self) -> Result<(), Error> {
+        if self.user_id.is_empty() {
+            return Err(Error::InvalidUserId(self.user_id.clone()));
+        }
+
+        if self.resource_id.is_empty() {
+            return Err(Error::InvalidResourceId(self.resource_id.clone()));
+        }
+
+        validate_range(self.start.as_ref(), self.end.as_ref())?;
+
+        Ok(())
+    }
+}
+
+impl FromRow<'_, PgRow> for WaitlistEntry {
+    fn from_row(row: &PgRow) -> Result<Self, sqlx::Error> {
+        let range: PgRange<DateTime<Utc>> = row.get("timespan");
+        let range: NaiveRange<DateTime<Utc>> = range.into();
+
+        Ok(Self {
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            resource_id: row.get("resource_id"),
+            start: range.start.map(|v| convert_to_timestamp(&v)),
+            end: range.end.map(|v| convert_to_timestamp(&v)),
+            note: row.get::<Option<String>, _>("note").unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waitlist_entry_should_validate_and_convert_to_reservation() {
+        let entry = WaitlistEntry::new(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "waiting for the device",
+        );
+        assert!(entry.validate().is_ok());
+
+        let rsvp = entry.to_reservation();
+        assert_eq!(
+            rsvp,
+            Reservation::new_pending(
+                "tyr",
+                "ixia-3230",
+                "2022-12-26T15:00:00-0700".parse().unwrap(),
+                "2022-12-30T12:00:00-0700".parse().unwrap(),
+                "waiting for the device",
+            )
+        );
+
+        let entry = WaitlistEntry {
+            end: entry.start.clone(),
+            ..entry
+        };
+        assert_eq!(entry.validate().unwrap_err(), Error::InvalidTime);
+        let entry = WaitlistEntry {
+            user_id: "".into(),
+            ..entry
+        };
+        assert_eq!(
+            entry.validate().unwrap_err(),
+            Error::InvalidUserId("".into())
+        );
+    }
+}
@@ -0,0 +1,20 @@
+-- Add down migration script here
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, null, to_jsonb(new), 'create');
+    ELSIF TG_OP = 'UPDATE' THEN
+        -- if status or timespan changed, update reservation_changes
+        IF OLD.status <> NEW.status OR OLD.timespan <> NEW.timespan THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, to_jsonb(old), to_jsonb(new), 'update');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (OLD.id, to_jsonb(old), null, 'delete');
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
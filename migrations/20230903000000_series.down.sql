@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX rsvp.reservations_series_id_idx;
+ALTER TABLE rsvp.reservations DROP COLUMN series_id;
+DROP SEQUENCE rsvp.reservation_series_id_seq;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+-- occurrences of a recurring reservation share the same series id
+CREATE SEQUENCE rsvp.reservation_series_id_seq;
+ALTER TABLE rsvp.reservations ADD COLUMN series_id BIGINT;
+
+CREATE INDEX reservations_series_id_idx ON rsvp.reservations (series_id);
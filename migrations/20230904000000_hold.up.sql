@@ -0,0 +1,5 @@
+-- Add up migration script here
+-- pending reservations not confirmed before hold_expires_at are deleted by the reaper
+ALTER TABLE rsvp.reservations ADD COLUMN hold_expires_at TIMESTAMPTZ;
+
+CREATE INDEX reservations_hold_expires_at_idx ON rsvp.reservations (hold_expires_at) WHERE status = 'pending';
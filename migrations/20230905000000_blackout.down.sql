@@ -0,0 +1,9 @@
+-- Add down migration script here
+DELETE FROM rsvp.reservations WHERE user_id IS NULL OR flagged;
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&);
+
+ALTER TABLE rsvp.reservations DROP COLUMN flagged;
+ALTER TABLE rsvp.reservations ALTER COLUMN user_id SET NOT NULL;
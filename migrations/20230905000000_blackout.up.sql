@@ -0,0 +1,10 @@
+-- Add up migration script here
+-- blackouts are blocked reservations without a user
+ALTER TABLE rsvp.reservations ALTER COLUMN user_id DROP NOT NULL;
+
+-- flagged pending reservations overlap a blackout, they don't hold their window until moved
+ALTER TABLE rsvp.reservations ADD COLUMN flagged BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (NOT flagged);
@@ -0,0 +1,2 @@
+-- Add down migration script here
+-- postgres could not drop a value from an enum, 'cancelled' is left unused
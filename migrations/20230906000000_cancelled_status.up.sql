@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- the new value could not be used in the transaction adding it, see 20230906000001_soft_cancel
+ALTER TYPE rsvp.reservation_status ADD VALUE 'cancelled';
@@ -0,0 +1,28 @@
+-- Add up migration script here
+-- cancelled reservations are kept for history but don't hold their window anymore
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (NOT flagged AND status <> 'cancelled');
+
+-- a cancelled reservation is gone for the listeners, record it as a delete change
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, null, to_jsonb(new), 'create');
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.status <> 'cancelled' AND NEW.status = 'cancelled' THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, to_jsonb(old), to_jsonb(new), 'delete');
+        -- if status, timespan or resource changed, update reservation_changes
+        ELSIF OLD.status <> NEW.status OR OLD.timespan <> NEW.timespan OR OLD.resource_id <> NEW.resource_id THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, to_jsonb(old), to_jsonb(new), 'update');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (OLD.id, to_jsonb(old), null, 'delete');
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
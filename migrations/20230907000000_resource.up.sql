@@ -0,0 +1,19 @@
+-- Add up migration script here
+CREATE TABLE rsvp.resources (
+    id VARCHAR(64) NOT NULL,
+    name TEXT NOT NULL DEFAULT '',
+    type TEXT NOT NULL DEFAULT '',
+    attributes JSONB NOT NULL DEFAULT '{}',
+    -- inactive resources can't be reserved anymore, their reservations are kept
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+
+    CONSTRAINT resources_pkey PRIMARY KEY (id)
+);
+
+CREATE INDEX resources_type_idx ON rsvp.resources (type);
+
+-- resources already reserved become catalog entries, so the foreign key holds
+INSERT INTO rsvp.resources (id, name) SELECT DISTINCT resource_id, resource_id FROM rsvp.reservations;
+
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_resource_id_fkey
+    FOREIGN KEY (resource_id) REFERENCES rsvp.resources (id);
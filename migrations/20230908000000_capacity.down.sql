@@ -0,0 +1,12 @@
+-- Add down migration script here
+DROP TRIGGER resources_capacity ON rsvp.resources;
+DROP FUNCTION rsvp.resources_capacity();
+DROP TRIGGER reservations_capacity ON rsvp.reservations;
+DROP FUNCTION rsvp.reservations_capacity();
+DROP FUNCTION rsvp.saturated(text, tstzrange, bigint);
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (NOT flagged AND status <> 'cancelled');
+ALTER TABLE rsvp.reservations DROP COLUMN pooled;
+ALTER TABLE rsvp.resources DROP COLUMN capacity;
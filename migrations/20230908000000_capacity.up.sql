@@ -0,0 +1,74 @@
+-- Add up migration script here
+-- pooled resources (desks, license seats) accept up to capacity overlapping reservations
+ALTER TABLE rsvp.resources ADD COLUMN capacity INT NOT NULL DEFAULT 1 CHECK (capacity > 0);
+
+-- reservations on pooled resources are checked by reservations_capacity() instead of the exclusion
+ALTER TABLE rsvp.reservations ADD COLUMN pooled BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (NOT flagged AND status <> 'cancelled' AND NOT pooled);
+
+-- sub-windows of during in which the resource holds as many reservations as its capacity.
+-- the boundaries of the held windows cut during into points and open gaps, each counted on its own
+CREATE OR REPLACE FUNCTION rsvp.saturated(rid text, during tstzrange, skip bigint DEFAULT 0)
+RETURNS tstzmultirange AS $$
+    WITH held AS (
+        SELECT timespan * during AS t FROM rsvp.reservations
+        WHERE resource_id = rid AND timespan && during AND id <> skip AND status <> 'cancelled' AND NOT flagged
+    ), points AS (
+        SELECT lower(t) AS p FROM held UNION SELECT upper(t) FROM held
+    ), pieces AS (
+        SELECT tstzrange(p, p, '[]') AS piece FROM points
+        UNION ALL
+        SELECT tstzrange(p, next, '()') FROM (SELECT p, lead(p) OVER (ORDER BY p) AS next FROM points) gaps
+        WHERE next IS NOT NULL
+    )
+    SELECT COALESCE(range_agg(piece), '{}') FROM pieces
+    WHERE NOT isempty(piece)
+        AND (SELECT count(*) FROM held WHERE t && piece)
+            >= (SELECT COALESCE(max(capacity), 1) FROM rsvp.resources WHERE id = rid)
+$$ LANGUAGE sql STABLE;
+
+-- the reservations of a pool are serialized by locking the resource, the check then sees every
+-- committed reservation. a full pool fails like the exclusion does, the existing key being the
+-- first saturated sub-window
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity() RETURNS TRIGGER AS $$
+DECLARE
+    saturated tstzmultirange;
+BEGIN
+    NEW.pooled := COALESCE((SELECT capacity > 1 FROM rsvp.resources WHERE id = NEW.resource_id), FALSE);
+    IF NOT NEW.pooled OR NEW.status = 'cancelled' OR NEW.flagged THEN
+        RETURN NEW;
+    END IF;
+
+    PERFORM 1 FROM rsvp.resources WHERE id = NEW.resource_id FOR NO KEY UPDATE;
+    saturated := rsvp.saturated(NEW.resource_id, NEW.timespan, NEW.id);
+    IF NOT isempty(saturated) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            MESSAGE = format('capacity of resource %s is exhausted in %s', NEW.resource_id, saturated),
+            DETAIL = format(
+                'Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s).',
+                NEW.resource_id, NEW.timespan, NEW.resource_id, (SELECT r FROM unnest(saturated) AS r LIMIT 1)
+            );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER reservations_capacity BEFORE INSERT OR UPDATE OF resource_id, timespan, status, flagged, pooled
+    ON rsvp.reservations FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_capacity();
+
+-- a capacity change moves the reservations of the resource in or out of the exclusion
+CREATE OR REPLACE FUNCTION rsvp.resources_capacity() RETURNS TRIGGER AS $$
+BEGIN
+    UPDATE rsvp.reservations SET pooled = NEW.capacity > 1 WHERE resource_id = NEW.id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER resources_capacity AFTER UPDATE OF capacity ON rsvp.resources
+    FOR EACH ROW WHEN (OLD.capacity <> NEW.capacity) EXECUTE PROCEDURE rsvp.resources_capacity();
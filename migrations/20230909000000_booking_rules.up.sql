@@ -0,0 +1,15 @@
+-- Add up migration script here
+-- a resource without rules accepts any reservation, a zero duration or lead time means no limit
+CREATE TABLE rsvp.booking_rules (
+    resource_id VARCHAR(64) NOT NULL,
+    -- IANA time zone the opening hours are expressed in
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    -- [{"weekday": 1, "opens": 540, "closes": 1080}], minutes since midnight, empty means always open
+    opening_hours JSONB NOT NULL DEFAULT '[]',
+    min_duration BIGINT NOT NULL DEFAULT 0,
+    max_duration BIGINT NOT NULL DEFAULT 0,
+    max_lead_time BIGINT NOT NULL DEFAULT 0,
+
+    CONSTRAINT booking_rules_pkey PRIMARY KEY (resource_id),
+    CONSTRAINT booking_rules_resource_id_fkey FOREIGN KEY (resource_id) REFERENCES rsvp.resources (id)
+);
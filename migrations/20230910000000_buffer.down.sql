@@ -0,0 +1,56 @@
+-- Add down migration script here
+DROP TRIGGER reservations_buffer ON rsvp.reservations;
+DROP FUNCTION rsvp.reservations_buffer();
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, timespan WITH &&) WHERE (NOT flagged AND status <> 'cancelled' AND NOT pooled);
+
+CREATE OR REPLACE FUNCTION rsvp.saturated(rid text, during tstzrange, skip bigint DEFAULT 0)
+RETURNS tstzmultirange AS $$
+    WITH held AS (
+        SELECT timespan * during AS t FROM rsvp.reservations
+        WHERE resource_id = rid AND timespan && during AND id <> skip AND status <> 'cancelled' AND NOT flagged
+    ), points AS (
+        SELECT lower(t) AS p FROM held UNION SELECT upper(t) FROM held
+    ), pieces AS (
+        SELECT tstzrange(p, p, '[]') AS piece FROM points
+        UNION ALL
+        SELECT tstzrange(p, next, '()') FROM (SELECT p, lead(p) OVER (ORDER BY p) AS next FROM points) gaps
+        WHERE next IS NOT NULL
+    )
+    SELECT COALESCE(range_agg(piece), '{}') FROM pieces
+    WHERE NOT isempty(piece)
+        AND (SELECT count(*) FROM held WHERE t && piece)
+            >= (SELECT COALESCE(max(capacity), 1) FROM rsvp.resources WHERE id = rid)
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity() RETURNS TRIGGER AS $$
+DECLARE
+    saturated tstzmultirange;
+BEGIN
+    NEW.pooled := COALESCE((SELECT capacity > 1 FROM rsvp.resources WHERE id = NEW.resource_id), FALSE);
+    IF NOT NEW.pooled OR NEW.status = 'cancelled' OR NEW.flagged THEN
+        RETURN NEW;
+    END IF;
+
+    PERFORM 1 FROM rsvp.resources WHERE id = NEW.resource_id FOR NO KEY UPDATE;
+    saturated := rsvp.saturated(NEW.resource_id, NEW.timespan, NEW.id);
+    IF NOT isempty(saturated) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            MESSAGE = format('capacity of resource %s is exhausted in %s', NEW.resource_id, saturated),
+            DETAIL = format(
+                'Key (resource_id, timespan)=(%s, %s) conflicts with existing key (resource_id, timespan)=(%s, %s).',
+                NEW.resource_id, NEW.timespan, NEW.resource_id, (SELECT r FROM unnest(saturated) AS r LIMIT 1)
+            );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE rsvp.reservations DROP COLUMN padded_timespan;
+ALTER TABLE rsvp.resources DROP COLUMN buffer_after;
+ALTER TABLE rsvp.resources DROP COLUMN buffer_before;
@@ -0,0 +1,77 @@
+-- Add up migration script here
+-- seconds a resource needs before and after every reservation, e.g. to clean a room or reset a rig
+ALTER TABLE rsvp.resources ADD COLUMN buffer_before BIGINT NOT NULL DEFAULT 0 CHECK (buffer_before >= 0);
+ALTER TABLE rsvp.resources ADD COLUMN buffer_after BIGINT NOT NULL DEFAULT 0 CHECK (buffer_after >= 0);
+
+-- the window a reservation blocks on its resource: the requested timespan padded with the buffers
+-- the resource had when the reservation was made or moved. blackouts are never padded
+ALTER TABLE rsvp.reservations ADD COLUMN padded_timespan TSTZRANGE;
+UPDATE rsvp.reservations SET padded_timespan = timespan;
+ALTER TABLE rsvp.reservations ALTER COLUMN padded_timespan SET NOT NULL;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_buffer() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.user_id IS NULL THEN
+        NEW.padded_timespan := NEW.timespan;
+    ELSE
+        SELECT tstzrange(
+            lower(NEW.timespan) - COALESCE(max(buffer_before), 0) * interval '1 second',
+            upper(NEW.timespan) + COALESCE(max(buffer_after), 0) * interval '1 second'
+        ) INTO NEW.padded_timespan FROM rsvp.resources WHERE id = NEW.resource_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- fired before reservations_capacity, which checks the padded window
+CREATE TRIGGER reservations_buffer BEFORE INSERT OR UPDATE OF resource_id, timespan
+    ON rsvp.reservations FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_buffer();
+
+ALTER TABLE rsvp.reservations DROP CONSTRAINT reservations_conflict;
+ALTER TABLE rsvp.reservations ADD CONSTRAINT reservations_conflict
+    EXCLUDE USING gist (resource_id WITH =, padded_timespan WITH &&) WHERE (NOT flagged AND status <> 'cancelled' AND NOT pooled);
+
+CREATE OR REPLACE FUNCTION rsvp.saturated(rid text, during tstzrange, skip bigint DEFAULT 0)
+RETURNS tstzmultirange AS $$
+    WITH held AS (
+        SELECT padded_timespan * during AS t FROM rsvp.reservations
+        WHERE resource_id = rid AND padded_timespan && during AND id <> skip AND status <> 'cancelled' AND NOT flagged
+    ), points AS (
+        SELECT lower(t) AS p FROM held UNION SELECT upper(t) FROM held
+    ), pieces AS (
+        SELECT tstzrange(p, p, '[]') AS piece FROM points
+        UNION ALL
+        SELECT tstzrange(p, next, '()') FROM (SELECT p, lead(p) OVER (ORDER BY p) AS next FROM points) gaps
+        WHERE next IS NOT NULL
+    )
+    SELECT COALESCE(range_agg(piece), '{}') FROM pieces
+    WHERE NOT isempty(piece)
+        AND (SELECT count(*) FROM held WHERE t && piece)
+            >= (SELECT COALESCE(max(capacity), 1) FROM rsvp.resources WHERE id = rid)
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity() RETURNS TRIGGER AS $$
+DECLARE
+    saturated tstzmultirange;
+BEGIN
+    NEW.pooled := COALESCE((SELECT capacity > 1 FROM rsvp.resources WHERE id = NEW.resource_id), FALSE);
+    IF NOT NEW.pooled OR NEW.status = 'cancelled' OR NEW.flagged THEN
+        RETURN NEW;
+    END IF;
+
+    PERFORM 1 FROM rsvp.resources WHERE id = NEW.resource_id FOR NO KEY UPDATE;
+    saturated := rsvp.saturated(NEW.resource_id, NEW.padded_timespan, NEW.id);
+    IF NOT isempty(saturated) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            MESSAGE = format('capacity of resource %s is exhausted in %s', NEW.resource_id, saturated),
+            DETAIL = format(
+                'Key (resource_id, padded_timespan)=(%s, %s) conflicts with existing key (resource_id, padded_timespan)=(%s, %s).',
+                NEW.resource_id, NEW.padded_timespan, NEW.resource_id, (SELECT r FROM unnest(saturated) AS r LIMIT 1)
+            );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,10 @@
+-- Add down migration script here
+DROP TRIGGER reservations_hierarchy ON rsvp.reservations;
+DROP FUNCTION rsvp.reservations_hierarchy();
+DROP TRIGGER resources_hierarchy ON rsvp.resources;
+DROP FUNCTION rsvp.resources_hierarchy();
+DROP FUNCTION rsvp.blocked_by_related(text, tstzrange, bigint);
+DROP FUNCTION rsvp.related_resources(text);
+DROP FUNCTION rsvp.descendant_resources(text);
+DROP FUNCTION rsvp.ancestor_resources(text);
+ALTER TABLE rsvp.resources DROP COLUMN parent_id;
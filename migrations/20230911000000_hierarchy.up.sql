@@ -0,0 +1,100 @@
+-- Add up migration script here
+-- a resource may be part of another one, e.g. the benches of a lab. a reservation blocks the
+-- ancestors and the descendants of its resource as well
+ALTER TABLE rsvp.resources ADD COLUMN parent_id VARCHAR(64);
+ALTER TABLE rsvp.resources ADD CONSTRAINT resources_parent_id_fkey
+    FOREIGN KEY (parent_id) REFERENCES rsvp.resources (id);
+CREATE INDEX resources_parent_id_idx ON rsvp.resources (parent_id);
+
+CREATE OR REPLACE FUNCTION rsvp.ancestor_resources(rid text) RETURNS SETOF text AS $$
+    WITH RECURSIVE ancestors AS (
+        SELECT parent_id AS id FROM rsvp.resources WHERE id = rid AND parent_id IS NOT NULL
+        UNION
+        SELECT r.parent_id FROM rsvp.resources r JOIN ancestors a ON r.id = a.id WHERE r.parent_id IS NOT NULL
+    )
+    SELECT id FROM ancestors
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION rsvp.descendant_resources(rid text) RETURNS SETOF text AS $$
+    WITH RECURSIVE descendants AS (
+        SELECT id FROM rsvp.resources WHERE parent_id = rid
+        UNION
+        SELECT r.id FROM rsvp.resources r JOIN descendants d ON r.parent_id = d.id
+    )
+    SELECT id FROM descendants
+$$ LANGUAGE sql STABLE;
+
+-- every ancestor and descendant of a resource, not the resource itself
+CREATE OR REPLACE FUNCTION rsvp.related_resources(rid text) RETURNS SETOF text AS $$
+    SELECT rsvp.ancestor_resources(rid) UNION SELECT rsvp.descendant_resources(rid)
+$$ LANGUAGE sql STABLE;
+
+-- windows of during blocked by the reservations of the ancestors and descendants of a resource
+CREATE OR REPLACE FUNCTION rsvp.blocked_by_related(rid text, during tstzrange, skip bigint DEFAULT 0)
+RETURNS tstzmultirange AS $$
+    SELECT COALESCE(range_agg(padded_timespan * during), '{}') FROM rsvp.reservations
+    WHERE resource_id IN (SELECT rsvp.related_resources(rid)) AND padded_timespan && during
+        AND id <> skip AND status <> 'cancelled' AND NOT flagged
+$$ LANGUAGE sql STABLE;
+
+-- a resource can't be its own ancestor
+CREATE OR REPLACE FUNCTION rsvp.resources_hierarchy() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.parent_id = NEW.id OR NEW.parent_id IN (SELECT rsvp.descendant_resources(NEW.id)) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'check_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'resources',
+            MESSAGE = format('resource %s can''t be a part of itself', NEW.id),
+            DETAIL = NEW.parent_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER resources_hierarchy BEFORE INSERT OR UPDATE OF parent_id ON rsvp.resources
+    FOR EACH ROW WHEN (NEW.parent_id IS NOT NULL) EXECUTE PROCEDURE rsvp.resources_hierarchy();
+
+-- the exclusion only sees one resource. the reservations of a hierarchy are serialized by locking
+-- its top resource, then checked against the ancestors and descendants. a conflict fails like the
+-- exclusion does, the existing key being the first conflicting reservation on another resource
+CREATE OR REPLACE FUNCTION rsvp.reservations_hierarchy() RETURNS TRIGGER AS $$
+DECLARE
+    existing rsvp.reservations;
+BEGIN
+    IF NEW.status = 'cancelled' OR NEW.flagged OR NOT EXISTS (
+        SELECT 1 FROM rsvp.resources
+        WHERE (id = NEW.resource_id AND parent_id IS NOT NULL) OR parent_id = NEW.resource_id
+    ) THEN
+        RETURN NEW;
+    END IF;
+
+    PERFORM 1 FROM rsvp.resources
+    WHERE id = COALESCE(
+        (SELECT a FROM rsvp.ancestor_resources(NEW.resource_id) AS a
+         WHERE (SELECT parent_id FROM rsvp.resources WHERE id = a) IS NULL),
+        NEW.resource_id
+    ) FOR NO KEY UPDATE;
+
+    SELECT * INTO existing FROM rsvp.reservations
+    WHERE resource_id IN (SELECT rsvp.related_resources(NEW.resource_id))
+        AND padded_timespan && NEW.padded_timespan AND id <> NEW.id AND status <> 'cancelled' AND NOT flagged
+    ORDER BY lower(padded_timespan), id LIMIT 1;
+    IF FOUND THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            MESSAGE = format('reservation on %s conflicts with reservation %s on %s', NEW.resource_id, existing.id, existing.resource_id),
+            DETAIL = format(
+                'Key (resource_id, padded_timespan)=(%s, %s) conflicts with existing key (resource_id, padded_timespan)=(%s, %s).',
+                NEW.resource_id, NEW.padded_timespan, existing.resource_id, existing.padded_timespan
+            );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+-- fired after reservations_buffer, which pads the window
+CREATE TRIGGER reservations_hierarchy BEFORE INSERT OR UPDATE OF resource_id, timespan, status, flagged
+    ON rsvp.reservations FOR EACH ROW EXECUTE PROCEDURE rsvp.reservations_hierarchy();
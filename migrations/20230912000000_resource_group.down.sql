@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE rsvp.resource_group_members;
+DROP TABLE rsvp.resource_groups;
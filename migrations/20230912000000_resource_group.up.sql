@@ -0,0 +1,23 @@
+-- Add up migration script here
+-- named sets of interchangeable resources, reserve_any books whichever member is free
+CREATE TABLE rsvp.resource_groups (
+    id VARCHAR(64) NOT NULL,
+    name TEXT NOT NULL DEFAULT '',
+    -- member picked last by reserve_any, round robin starts after it
+    last_resource_id VARCHAR(64),
+
+    CONSTRAINT resource_groups_pkey PRIMARY KEY (id)
+);
+
+CREATE TABLE rsvp.resource_group_members (
+    group_id VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    -- order of the members, first fit tries them in this order
+    position INT NOT NULL,
+
+    CONSTRAINT resource_group_members_pkey PRIMARY KEY (group_id, resource_id),
+    CONSTRAINT resource_group_members_group_id_fkey FOREIGN KEY (group_id)
+        REFERENCES rsvp.resource_groups (id) ON DELETE CASCADE,
+    CONSTRAINT resource_group_members_resource_id_fkey FOREIGN KEY (resource_id)
+        REFERENCES rsvp.resources (id)
+);
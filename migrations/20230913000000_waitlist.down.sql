@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP TRIGGER reservations_waitlist ON rsvp.reservations;
+DROP FUNCTION rsvp.reservations_waitlist();
+DROP TABLE rsvp.waitlist;
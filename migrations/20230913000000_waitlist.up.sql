@@ -0,0 +1,53 @@
+-- Add up migration script here
+-- requests queued for a window that was taken, served in the order they joined
+CREATE TABLE rsvp.waitlist (
+    id BIGSERIAL NOT NULL,
+    user_id VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(64) NOT NULL,
+    timespan TSTZRANGE NOT NULL,
+    note TEXT,
+
+    CONSTRAINT waitlist_pkey PRIMARY KEY (id),
+    CONSTRAINT waitlist_resource_id_fkey FOREIGN KEY (resource_id)
+        REFERENCES rsvp.resources (id),
+    -- joining twice keeps the place in the queue
+    CONSTRAINT waitlist_entry_key UNIQUE (user_id, resource_id, timespan)
+);
+
+CREATE INDEX waitlist_resource_id_timespan_idx ON rsvp.waitlist USING gist (resource_id, timespan);
+
+-- a cancelled or deleted reservation frees its window. the waiting requests overlapping it, on its
+-- resource or a related one, are promoted to pending reservations in the order they joined. a
+-- request still conflicting keeps its place, a promoted one is reported to the listeners as a
+-- created reservation by rsvp.reservations_trigger()
+CREATE OR REPLACE FUNCTION rsvp.reservations_waitlist() RETURNS TRIGGER AS $$
+DECLARE
+    entry rsvp.waitlist;
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.status <> 'cancelled' THEN
+        RETURN NULL;
+    END IF;
+
+    -- entries locked by another promotion are waited for, and skipped once it deleted them
+    FOR entry IN
+        SELECT w.* FROM rsvp.waitlist w JOIN rsvp.resources r ON r.id = w.resource_id
+        WHERE (w.resource_id = OLD.resource_id OR w.resource_id IN (SELECT rsvp.related_resources(OLD.resource_id)))
+            AND w.timespan && OLD.padded_timespan AND upper(w.timespan) > now() AND r.active
+        ORDER BY w.id FOR UPDATE OF w
+    LOOP
+        BEGIN
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
+            VALUES (entry.user_id, entry.resource_id, entry.timespan, entry.note, 'pending');
+            DELETE FROM rsvp.waitlist WHERE id = entry.id;
+        EXCEPTION WHEN exclusion_violation THEN
+            -- still taken, try the next one
+        END;
+    END LOOP;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- fired after reservations_trigger, so the cancellation is recorded before the promotions
+CREATE TRIGGER reservations_waitlist AFTER UPDATE OF status OR DELETE ON rsvp.reservations
+    FOR EACH ROW WHEN (OLD.status <> 'cancelled' AND NOT OLD.flagged)
+    EXECUTE PROCEDURE rsvp.reservations_waitlist();
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP INDEX rsvp.reservation_changes_xid_id_idx;
+ALTER TABLE rsvp.reservation_changes DROP COLUMN xid;
@@ -0,0 +1,6 @@
+-- Add up migration script here
+-- change ids are taken before commit, a change may become visible after a higher id was read.
+-- listeners read the changes in (xid, id) order, only once every transaction before them ended
+ALTER TABLE rsvp.reservation_changes ADD COLUMN xid xid8 NOT NULL DEFAULT pg_current_xact_id();
+
+CREATE INDEX reservation_changes_xid_id_idx ON rsvp.reservation_changes (xid, id);
@@ -0,0 +1,57 @@
+-- Add up migration script here
+-- a blackout takes the resource offline: on a pool it takes every unit, not one
+CREATE OR REPLACE FUNCTION rsvp.saturated(rid text, during tstzrange, skip bigint DEFAULT 0)
+RETURNS tstzmultirange AS $$
+    WITH pool AS (
+        SELECT COALESCE(max(capacity), 1) AS capacity FROM rsvp.resources WHERE id = rid
+    ), held AS (
+        SELECT padded_timespan * during AS t,
+            CASE WHEN user_id IS NULL THEN (SELECT capacity FROM pool) ELSE 1 END AS units
+        FROM rsvp.reservations
+        WHERE resource_id = rid AND padded_timespan && during AND id <> skip AND status <> 'cancelled' AND NOT flagged
+    ), points AS (
+        SELECT lower(t) AS p FROM held UNION SELECT upper(t) FROM held
+    ), pieces AS (
+        SELECT tstzrange(p, p, '[]') AS piece FROM points
+        UNION ALL
+        SELECT tstzrange(p, next, '()') FROM (SELECT p, lead(p) OVER (ORDER BY p) AS next FROM points) gaps
+        WHERE next IS NOT NULL
+    )
+    SELECT COALESCE(range_agg(piece), '{}') FROM pieces
+    WHERE NOT isempty(piece)
+        AND (SELECT sum(units) FROM held WHERE t && piece) >= (SELECT capacity FROM pool)
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION rsvp.reservations_capacity() RETURNS TRIGGER AS $$
+DECLARE
+    saturated tstzmultirange;
+BEGIN
+    NEW.pooled := COALESCE((SELECT capacity > 1 FROM rsvp.resources WHERE id = NEW.resource_id), FALSE);
+    IF NOT NEW.pooled OR NEW.status = 'cancelled' OR NEW.flagged THEN
+        RETURN NEW;
+    END IF;
+
+    PERFORM 1 FROM rsvp.resources WHERE id = NEW.resource_id FOR NO KEY UPDATE;
+    IF NEW.user_id IS NULL THEN
+        -- a blackout needs every unit, any reservation it overlaps is in the way
+        SELECT COALESCE(range_agg(padded_timespan * NEW.padded_timespan), '{}') INTO saturated
+        FROM rsvp.reservations
+        WHERE resource_id = NEW.resource_id AND padded_timespan && NEW.padded_timespan AND id <> NEW.id
+            AND status <> 'cancelled' AND NOT flagged;
+    ELSE
+        saturated := rsvp.saturated(NEW.resource_id, NEW.padded_timespan, NEW.id);
+    END IF;
+    IF NOT isempty(saturated) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'exclusion_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'reservations',
+            MESSAGE = format('capacity of resource %s is exhausted in %s', NEW.resource_id, saturated),
+            DETAIL = format(
+                'Key (resource_id, padded_timespan)=(%s, %s) conflicts with existing key (resource_id, padded_timespan)=(%s, %s).',
+                NEW.resource_id, NEW.padded_timespan, NEW.resource_id, (SELECT r FROM unnest(saturated) AS r LIMIT 1)
+            );
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
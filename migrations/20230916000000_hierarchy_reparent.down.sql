@@ -0,0 +1,15 @@
+-- Add down migration script here
+-- a resource can't be its own ancestor
+CREATE OR REPLACE FUNCTION rsvp.resources_hierarchy() RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.parent_id = NEW.id OR NEW.parent_id IN (SELECT rsvp.descendant_resources(NEW.id)) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'check_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'resources',
+            MESSAGE = format('resource %s can''t be a part of itself', NEW.id),
+            DETAIL = NEW.parent_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,47 @@
+-- Add up migration script here
+-- a resource can't be its own ancestor, and moving it under another parent can't make its
+-- reservations or the ones of its descendants conflict with the reservations of its new ancestors
+CREATE OR REPLACE FUNCTION rsvp.resources_hierarchy() RETURNS TRIGGER AS $$
+DECLARE
+    existing rsvp.reservations;
+BEGIN
+    IF NEW.parent_id = NEW.id OR NEW.parent_id IN (SELECT rsvp.descendant_resources(NEW.id)) THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'check_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'resources',
+            MESSAGE = format('resource %s can''t be a part of itself', NEW.id),
+            DETAIL = NEW.parent_id;
+    END IF;
+    IF TG_OP = 'INSERT' OR NEW.parent_id IS NOT DISTINCT FROM OLD.parent_id THEN
+        RETURN NEW;
+    END IF;
+
+    -- serialized with the reservations of the new hierarchy, see rsvp.reservations_hierarchy()
+    PERFORM 1 FROM rsvp.resources
+    WHERE id = COALESCE(
+        (SELECT a FROM rsvp.ancestor_resources(NEW.parent_id) AS a
+         WHERE (SELECT parent_id FROM rsvp.resources WHERE id = a) IS NULL),
+        NEW.parent_id
+    ) FOR NO KEY UPDATE;
+
+    SELECT * INTO existing FROM rsvp.reservations r
+    WHERE r.resource_id IN (SELECT NEW.parent_id UNION SELECT rsvp.ancestor_resources(NEW.parent_id))
+        AND r.status <> 'cancelled' AND NOT r.flagged
+        AND EXISTS (
+            SELECT 1 FROM rsvp.reservations s
+            WHERE s.resource_id IN (SELECT NEW.id UNION SELECT rsvp.descendant_resources(NEW.id))
+                AND s.padded_timespan && r.padded_timespan AND s.status <> 'cancelled' AND NOT s.flagged
+        )
+    ORDER BY lower(r.padded_timespan), r.id LIMIT 1;
+    IF FOUND THEN
+        RAISE EXCEPTION USING
+            ERRCODE = 'check_violation',
+            SCHEMA = 'rsvp',
+            TABLE = 'resources',
+            MESSAGE = format('reservations of %s conflict with reservation %s on %s', NEW.id, existing.id, existing.resource_id),
+            DETAIL = NEW.parent_id;
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
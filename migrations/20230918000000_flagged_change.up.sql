@@ -0,0 +1,25 @@
+-- Add up migration script here
+-- flagging a reservation, or taking the flag off, changes whether it holds its window. it is
+-- recorded as an update change, so the listeners find out a blackout flagged their reservation
+CREATE OR REPLACE FUNCTION rsvp.reservations_trigger() RETURNS TRIGGER AS $$
+BEGIN
+    IF TG_OP = 'INSERT' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, null, to_jsonb(new), 'create');
+    ELSIF TG_OP = 'UPDATE' THEN
+        IF OLD.status <> 'cancelled' AND NEW.status = 'cancelled' THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, to_jsonb(old), to_jsonb(new), 'delete');
+        -- if status, timespan, resource or flag changed, update reservation_changes
+        ELSIF OLD.status <> NEW.status OR OLD.timespan <> NEW.timespan OR OLD.resource_id <> NEW.resource_id
+            OR OLD.flagged IS DISTINCT FROM NEW.flagged THEN
+            INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (NEW.id, to_jsonb(old), to_jsonb(new), 'update');
+        END IF;
+    ELSIF TG_OP = 'DELETE' THEN
+        -- update reservation_changes
+        INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) VALUES (OLD.id, to_jsonb(old), null, 'delete');
+    END IF;
+    -- notify a channel called reservation_update
+    NOTIFY reservation_update;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
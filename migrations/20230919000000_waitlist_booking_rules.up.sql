@@ -0,0 +1,81 @@
+-- Add up migration script here
+-- whether a window follows the booking rules of a resource, the same check as BookingRules::check:
+-- its duration, how far ahead it starts, and the opening hours of the day it starts on in the
+-- time zone of the rules. a resource without rules accepts any window
+CREATE OR REPLACE FUNCTION rsvp.booking_rules_allow(rid TEXT, window_range TSTZRANGE) RETURNS BOOLEAN AS $$
+DECLARE
+    rules rsvp.booking_rules;
+    duration BIGINT;
+    local_start TIMESTAMP;
+    day_start TIMESTAMP;
+BEGIN
+    SELECT * INTO rules FROM rsvp.booking_rules WHERE resource_id = rid;
+    IF NOT FOUND THEN
+        RETURN TRUE;
+    END IF;
+
+    duration := extract(epoch FROM upper(window_range) - lower(window_range));
+    IF (rules.min_duration > 0 AND duration < rules.min_duration)
+        OR (rules.max_duration > 0 AND duration > rules.max_duration)
+        OR (rules.max_lead_time > 0 AND extract(epoch FROM lower(window_range) - now()) > rules.max_lead_time) THEN
+        RETURN FALSE;
+    END IF;
+
+    IF jsonb_array_length(rules.opening_hours) = 0 THEN
+        RETURN TRUE;
+    END IF;
+    local_start := lower(window_range) AT TIME ZONE rules.timezone;
+    day_start := date_trunc('day', local_start);
+    RETURN EXISTS (
+        SELECT 1 FROM jsonb_array_elements(rules.opening_hours) AS h
+        WHERE (h->>'weekday')::int = extract(isodow FROM local_start)
+            AND (h->>'opens')::bigint * 60 <= extract(epoch FROM local_start - day_start)
+            AND extract(epoch FROM (upper(window_range) AT TIME ZONE rules.timezone) - day_start) <= (h->>'closes')::bigint * 60
+    );
+END;
+$$ LANGUAGE plpgsql;
+
+-- a cancelled or deleted reservation frees its window. the waiting requests overlapping it, on its
+-- resource or a related one, are promoted to pending reservations in the order they joined. a
+-- request breaking the booking rules of its resource, or still conflicting, keeps its place and a
+-- warning is raised. requests overlapping a blackout are not promoted, even if it is still flagged
+-- while its policy makes room for it. a promoted one is reported to the listeners as a created
+-- reservation by rsvp.reservations_trigger()
+CREATE OR REPLACE FUNCTION rsvp.reservations_waitlist() RETURNS TRIGGER AS $$
+DECLARE
+    entry rsvp.waitlist;
+BEGIN
+    IF TG_OP = 'UPDATE' AND NEW.status <> 'cancelled' THEN
+        RETURN NULL;
+    END IF;
+
+    -- entries locked by another promotion are waited for, and skipped once it deleted them
+    FOR entry IN
+        SELECT w.* FROM rsvp.waitlist w JOIN rsvp.resources r ON r.id = w.resource_id
+        WHERE (w.resource_id = OLD.resource_id OR w.resource_id IN (SELECT rsvp.related_resources(OLD.resource_id)))
+            AND w.timespan && OLD.padded_timespan AND upper(w.timespan) > now() AND r.active
+            AND NOT EXISTS (
+                SELECT 1 FROM rsvp.reservations b
+                WHERE (b.resource_id = w.resource_id OR b.resource_id IN (SELECT rsvp.related_resources(w.resource_id)))
+                    AND b.padded_timespan && w.timespan AND b.user_id IS NULL AND b.status = 'blocked'
+            )
+        ORDER BY w.id FOR UPDATE OF w
+    LOOP
+        IF NOT rsvp.booking_rules_allow(entry.resource_id, entry.timespan) THEN
+            RAISE WARNING 'waitlist entry % not promoted: it breaks the booking rules of %', entry.id, entry.resource_id;
+            CONTINUE;
+        END IF;
+
+        BEGIN
+            INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status)
+            VALUES (entry.user_id, entry.resource_id, entry.timespan, entry.note, 'pending');
+            DELETE FROM rsvp.waitlist WHERE id = entry.id;
+        -- still taken, as told by the exclusion constraint or the capacity, buffer and hierarchy
+        -- triggers. anything else is a bug and fails the cancellation
+        EXCEPTION WHEN exclusion_violation OR check_violation OR raise_exception THEN
+            RAISE WARNING 'waitlist entry % not promoted: %', entry.id, SQLERRM;
+        END;
+    END LOOP;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -1,14 +1,15 @@
-use sqlx_postgres::PgListener;
+use reservation::{ReservationManager, Rsvp};
+use sqlx::PgPool;
 
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
     let url = std::env::var("DATABASE_URL").expect("DATAVASE_URL must be set");
-    let mut listener = PgListener::connect(&url).await.unwrap();
-    listener.listen("reservation_update").await.unwrap();
-    println!("Listening for reservation_update events...");
-    loop {
-        let notification = listener.recv().await.unwrap();
-        println!("Received notification: {:?}", notification);
+    let pool = PgPool::connect(&url).await.unwrap();
+    let manager = ReservationManager::new(pool);
+    let mut changes = manager.listen(abi::ListenRequest::default()).await;
+    println!("Listening for reservation changes...");
+    while let Some(change) = changes.recv().await {
+        println!("Received change: {:?}", change);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,568 @@
+use crate::{Catalog, ReservationManager};
+use abi::{Normalizer, ResourceId, Validator};
+use async_trait::async_trait;
+use sqlx::{types::Json, PgConnection};
+
+/// groups with their members in order, as read by ResourceGroup::from_row
+const SELECT_RESOURCE_GROUPS: &str = "SELECT g.id, g.name, \
+    COALESCE(array_agg(m.resource_id ORDER BY m.position) FILTER (WHERE m.resource_id IS NOT NULL), '{}')::text[] AS resource_ids \
+    FROM rsvp.resource_groups g LEFT JOIN rsvp.resource_group_members m ON m.group_id = g.id";
+
+#[async_trait]
+impl Catalog for ReservationManager {
+    async fn create_resource(
+        &self,
+        mut resource: abi::Resource,
+    ) -> Result<abi::Resource, abi::Error> {
+        resource.normalize()?;
+        let created: Option<abi::Resource> = sqlx::query_as(
+            "INSERT INTO rsvp.resources (id, name, type, attributes, active, capacity, buffer_before, buffer_after, parent_id) \
+            VALUES ($1, $2, $3, $4, COALESCE($5, TRUE), $6, $7, $8, $9) \
+            ON CONFLICT (id) DO NOTHING RETURNING *",
+        )
+        .bind(&resource.id)
+        .bind(&resource.name)
+        .bind(&resource.r#type)
+        .bind(resource.get_attributes())
+        .bind(resource.active)
+        .bind(resource.capacity)
+        .bind(resource.buffer_before)
+        .bind(resource.buffer_after)
+        .bind(resource.get_parent_id())
+        .fetch_optional(&self.pool)
+        .await?;
+
+        created.ok_or(abi::Error::DuplicateResource(resource.id))
+    }
+
+    async fn update_resource(
+        &self,
+        mut resource: abi::Resource,
+    ) -> Result<abi::Resource, abi::Error> {
+        resource.normalize()?;
+        let updated = sqlx::query_as(
+            "UPDATE rsvp.resources SET name = $1, type = $2, attributes = $3, active = COALESCE($4, active), capacity = $5, \
+            buffer_before = $6, buffer_after = $7, parent_id = $8 WHERE id = $9 RETURNING *",
+        )
+        .bind(&resource.name)
+        .bind(&resource.r#type)
+        .bind(resource.get_attributes())
+        .bind(resource.active)
+        .bind(resource.capacity)
+        .bind(resource.buffer_before)
+        .bind(resource.buffer_after)
+        .bind(resource.get_parent_id())
+        .bind(&resource.id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(updated)
+    }
+
+    async fn get_resource(&self, id: ResourceId) -> Result<abi::Resource, abi::Error> {
+        if id.is_empty() {
+            return Err(abi::Error::InvalidResourceId(id));
+        }
+        let resource = sqlx::query_as("SELECT * FROM rsvp.resources WHERE id = $1")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(resource)
+    }
+
+    async fn list_resources(
+        &self,
+        req: abi::ListResourcesRequest,
+    ) -> Result<Vec<abi::Resource>, abi::Error> {
+        let resources = sqlx::query_as(
+            "SELECT * FROM rsvp.resources WHERE ($1 = '' OR type = $1) AND ($2 OR active) ORDER BY id",
+        )
+        .bind(req.r#type)
+        .bind(req.include_inactive)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(resources)
+    }
+
+    async fn deactivate_resource(&self, id: ResourceId) -> Result<abi::Resource, abi::Error> {
+        if id.is_empty() {
+            return Err(abi::Error::InvalidResourceId(id));
+        }
+        let resource =
+            sqlx::query_as("UPDATE rsvp.resources SET active = FALSE WHERE id = $1 RETURNING *")
+                .bind(id)
+                .fetch_one(&self.pool)
+                .await?;
+        Ok(resource)
+    }
+
+    async fn set_booking_rules(
+        &self,
+        mut rules: abi::BookingRules,
+    ) -> Result<abi::BookingRules, abi::Error> {
+        rules.normalize()?;
+        let rules: Option<abi::BookingRules> = sqlx::query_as(
+            "INSERT INTO rsvp.booking_rules (resource_id, timezone, opening_hours, min_duration, max_duration, max_lead_time) \
+            SELECT id, $2, $3, $4, $5, $6 FROM rsvp.resources WHERE id = $1 \
+            ON CONFLICT (resource_id) DO UPDATE SET timezone = EXCLUDED.timezone, opening_hours = EXCLUDED.opening_hours, \
+            min_duration = EXCLUDED.min_duration, max_duration = EXCLUDED.max_duration, max_lead_time = EXCLUDED.max_lead_time \
+            RETURNING *",
+        )
+        .bind(&rules.resource_id)
+        .bind(&rules.timezone)
+        .bind(Json(&rules.opening_hours))
+        .bind(rules.min_duration)
+        .bind(rules.max_duration)
+        .bind(rules.max_lead_time)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        rules.ok_or(abi::Error::NotFound)
+    }
+
+    async fn get_booking_rules(&self, id: ResourceId) -> Result<abi::BookingRules, abi::Error> {
+        // checks the resource exists as well
+        let resource = self.get_resource(id).await?;
+        let rules = sqlx::query_as("SELECT * FROM rsvp.booking_rules WHERE resource_id = $1")
+            .bind(&resource.id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(rules.unwrap_or_else(|| abi::BookingRules::new(resource.id)))
+    }
+
+    async fn create_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, abi::Error> {
+        group.validate()?;
+        let mut tx = self.pool.begin().await?;
+        let created: Option<String> = sqlx::query_scalar(
+            "INSERT INTO rsvp.resource_groups (id, name) VALUES ($1, $2) ON CONFLICT (id) DO NOTHING RETURNING id",
+        )
+        .bind(&group.id)
+        .bind(&group.name)
+        .fetch_optional(&mut *tx)
+        .await?;
+        if created.is_none() {
+            return Err(abi::Error::DuplicateResourceGroup(group.id));
+        }
+        insert_members(&mut tx, &group).await?;
+        tx.commit().await?;
+
+        self.get_resource_group(group.id).await
+    }
+
+    async fn update_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, abi::Error> {
+        group.validate()?;
+        let mut tx = self.pool.begin().await?;
+        sqlx::query_scalar::<_, String>(
+            "UPDATE rsvp.resource_groups SET name = $1 WHERE id = $2 RETURNING id",
+        )
+        .bind(&group.name)
+        .bind(&group.id)
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM rsvp.resource_group_members WHERE group_id = $1")
+            .bind(&group.id)
+            .execute(&mut *tx)
+            .await?;
+        insert_members(&mut tx, &group).await?;
+        tx.commit().await?;
+
+        self.get_resource_group(group.id).await
+    }
+
+    async fn get_resource_group(&self, id: String) -> Result<abi::ResourceGroup, abi::Error> {
+        if id.is_empty() {
+            return Err(abi::Error::InvalidResourceGroup("empty id".into()));
+        }
+        let group = sqlx::query_as(&format!(
+            "{} WHERE g.id = $1 GROUP BY g.id",
+            SELECT_RESOURCE_GROUPS
+        ))
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(group)
+    }
+
+    async fn list_resource_groups(&self) -> Result<Vec<abi::ResourceGroup>, abi::Error> {
+        let groups = sqlx::query_as(&format!(
+            "{} GROUP BY g.id ORDER BY g.id",
+            SELECT_RESOURCE_GROUPS
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(groups)
+    }
+}
+
+async fn insert_members(
+    conn: &mut PgConnection,
+    group: &abi::ResourceGroup,
+) -> Result<(), abi::Error> {
+    sqlx::query(
+        "INSERT INTO rsvp.resource_group_members (group_id, resource_id, position) \
+        SELECT $1, m.resource_id, m.position FROM unnest($2::text[]) WITH ORDINALITY AS m(resource_id, position)",
+    )
+    .bind(&group.id)
+    .bind(&group.resource_ids)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{manager::tests::get_tdb, Rsvp};
+    use abi::{BookingRules, OpeningHours, PolicyViolationReason, Resource, ResourceGroup};
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn resource_crud_should_work() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+
+        let mut resource = Resource::new("ixia-3230", "Ixia 3230", "device");
+        resource.attributes = r#"{"ports":8}"#.into();
+        let created = manager.create_resource(resource.clone()).await.unwrap();
+        assert_eq!(created, resource);
+
+        let err = manager.create_resource(resource.clone()).await.unwrap_err();
+        assert_eq!(err, abi::Error::DuplicateResource("ixia-3230".into()));
+
+        resource.name = "Ixia 3230 (lab 2)".into();
+        resource.attributes = "".into();
+        let updated = manager.update_resource(resource.clone()).await.unwrap();
+        assert_eq!(updated.name, "Ixia 3230 (lab 2)");
+        assert_eq!(updated.attributes, "{}");
+        assert_eq!(
+            manager.get_resource("ixia-3230".into()).await.unwrap(),
+            updated
+        );
+
+        let err = manager
+            .update_resource(Resource::new("ixia-9999", "", ""))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        let err = manager.get_resource("ixia-9999".into()).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn resource_without_active_should_be_active_or_unchanged() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+
+        let resource = Resource {
+            active: None,
+            ..Resource::new("ixia-3230", "Ixia 3230", "device")
+        };
+        let created = manager.create_resource(resource.clone()).await.unwrap();
+        assert_eq!(created.active, Some(true));
+
+        manager
+            .deactivate_resource("ixia-3230".into())
+            .await
+            .unwrap();
+        let updated = manager.update_resource(resource).await.unwrap();
+        assert_eq!(updated.active, Some(false));
+
+        let resource = Resource {
+            active: Some(true),
+            ..updated
+        };
+        let updated = manager.update_resource(resource).await.unwrap();
+        assert_eq!(updated.active, Some(true));
+    }
+
+    #[tokio::test]
+    async fn list_resources_should_filter_type_and_inactive() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        for resource in [
+            Resource::new("room-1", "Room 1", "room"),
+            Resource::new("room-2", "Room 2", "room"),
+            Resource::new("ixia-3230", "Ixia 3230", "device"),
+        ] {
+            manager.create_resource(resource).await.unwrap();
+        }
+        let deactivated = manager.deactivate_resource("room-2".into()).await.unwrap();
+        assert!(!deactivated.active());
+
+        let ids = |resources: Vec<Resource>| -> Vec<String> {
+            resources.into_iter().map(|r| r.id).collect()
+        };
+        let rooms = manager
+            .list_resources(abi::ListResourcesRequest::new("room", false))
+            .await
+            .unwrap();
+        assert_eq!(ids(rooms), ["room-1"]);
+        let all = manager
+            .list_resources(abi::ListResourcesRequest::new("", true))
+            .await
+            .unwrap();
+        assert_eq!(ids(all), ["ixia-3230", "room-1", "room-2"]);
+    }
+
+    #[tokio::test]
+    async fn reserve_should_reject_unknown_or_inactive_resource() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        manager
+            .create_resource(Resource::new("room-1", "Room 1", "room"))
+            .await
+            .unwrap();
+        let rsvp = |rid: &str| {
+            abi::Reservation::new_pending(
+                "tyrid",
+                rid,
+                "2023-12-25T15:00:00-0700".parse().unwrap(),
+                "2023-12-28T12:00:00-0700".parse().unwrap(),
+                "hello.",
+            )
+        };
+
+        let err = manager.reserve(rsvp("room-9")).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-9".into()));
+
+        let reserved = manager.reserve(rsvp("room-1")).await.unwrap();
+        manager.deactivate_resource("room-1".into()).await.unwrap();
+        let err = manager.reserve(rsvp("room-1")).await.unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-1".into()));
+
+        // existing reservations are kept, but can't move to an unknown resource
+        assert_eq!(manager.get(reserved.id).await.unwrap(), reserved);
+        let err = manager
+            .transfer(reserved.id, "room-9".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-9".into()));
+    }
+
+    #[tokio::test]
+    async fn resource_hierarchy_should_reject_cycles() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        manager
+            .create_resource(Resource::new("lab-1", "Lab 1", "lab"))
+            .await
+            .unwrap();
+        let bench = manager
+            .create_resource(Resource::new_child("bench-1", "Bench 1", "bench", "lab-1"))
+            .await
+            .unwrap();
+        assert_eq!(bench.parent_id, "lab-1");
+
+        let err = manager
+            .create_resource(Resource::new_child("bench-2", "Bench 2", "bench", "lab-9"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceParent("lab-9".into()));
+
+        let lab = Resource::new_child("lab-1", "Lab 1", "lab", "bench-1");
+        let err = manager.update_resource(lab).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceParent("bench-1".into()));
+
+        // a bench can move to the top level
+        let bench = manager
+            .update_resource(Resource::new("bench-1", "Bench 1", "bench"))
+            .await
+            .unwrap();
+        assert_eq!(bench.parent_id, "");
+    }
+
+    #[tokio::test]
+    async fn resource_hierarchy_should_reject_parent_with_conflicting_reservations() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        for resource in [
+            Resource::new("lab-1", "Lab 1", "lab"),
+            Resource::new("lab-2", "Lab 2", "lab"),
+            Resource::new("bench-1", "Bench 1", "bench"),
+            Resource::new_child("probe-1", "Probe 1", "probe", "bench-1"),
+        ] {
+            manager.create_resource(resource).await.unwrap();
+        }
+        let reserve = |rid: &str, start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                "aliceid",
+                rid,
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "",
+            )
+        };
+        manager
+            .reserve(reserve(
+                "lab-1",
+                "2024-03-01T10:00:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(reserve(
+                "lab-2",
+                "2024-03-01T13:00:00Z",
+                "2024-03-01T15:00:00Z",
+            ))
+            .await
+            .unwrap();
+        manager
+            .reserve(reserve(
+                "probe-1",
+                "2024-03-01T11:00:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+
+        // the probe of the bench is taken while lab 1 is
+        let bench = Resource::new_child("bench-1", "Bench 1", "bench", "lab-1");
+        let err = manager.update_resource(bench).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceParent("lab-1".into()));
+        let bench = manager.get_resource("bench-1".into()).await.unwrap();
+        assert_eq!(bench.parent_id, "");
+
+        let bench = Resource::new_child("bench-1", "Bench 1", "bench", "lab-2");
+        let bench = manager.update_resource(bench).await.unwrap();
+        assert_eq!(bench.parent_id, "lab-2");
+    }
+
+    #[tokio::test]
+    async fn resource_group_crud_should_work() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        for id in ["room-1", "room-2"] {
+            manager
+                .create_resource(Resource::new(id, id, "room"))
+                .await
+                .unwrap();
+        }
+
+        let group = ResourceGroup::new("rooms", "Rooms", ["room-2", "room-1"]);
+        let created = manager.create_resource_group(group.clone()).await.unwrap();
+        assert_eq!(created, group);
+        let err = manager
+            .create_resource_group(group.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::DuplicateResourceGroup("rooms".into()));
+
+        let err = manager
+            .create_resource_group(ResourceGroup::new("desks", "Desks", ["desk-1"]))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("desk-1".into()));
+        let err = manager
+            .get_resource_group("desks".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        let group = ResourceGroup::new("rooms", "Meeting rooms", ["room-1"]);
+        let updated = manager.update_resource_group(group.clone()).await.unwrap();
+        assert_eq!(updated, group);
+        let empty = ResourceGroup::new("empty", "Empty", Vec::<String>::new());
+        manager.create_resource_group(empty.clone()).await.unwrap();
+        assert_eq!(
+            manager.list_resource_groups().await.unwrap(),
+            vec![empty, group]
+        );
+
+        let err = manager
+            .update_resource_group(ResourceGroup::new("desks", "Desks", ["room-1"]))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn booking_rules_should_be_stored_and_enforced() {
+        let tdb = get_tdb();
+        let manager = ReservationManager::new(tdb.get_pool().await);
+        manager
+            .create_resource(Resource::new("room-1", "Room 1", "room"))
+            .await
+            .unwrap();
+
+        // a resource without rules accepts any reservation
+        let rules = manager.get_booking_rules("room-1".into()).await.unwrap();
+        assert_eq!(rules, BookingRules::new("room-1"));
+        let err = manager
+            .get_booking_rules("room-9".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        let err = manager
+            .set_booking_rules(BookingRules::new("room-9"))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+
+        // open every day from 09:00 to 18:00, no more than 30 days ahead
+        let rules = BookingRules {
+            opening_hours: (1..=7)
+                .map(|weekday| OpeningHours::new(weekday, "09:00", "18:00").unwrap())
+                .collect(),
+            min_duration: 30 * 60,
+            max_duration: 4 * 3600,
+            max_lead_time: 30 * 86400,
+            ..BookingRules::new("room-1")
+        };
+        let stored = manager.set_booking_rules(rules.clone()).await.unwrap();
+        assert_eq!(stored, rules);
+        assert_eq!(
+            manager.get_booking_rules("room-1".into()).await.unwrap(),
+            rules
+        );
+
+        let tomorrow = (Utc::now() + Duration::days(1)).date_naive();
+        let rsvp = |days: i64, start: u32, end: u32| {
+            let day = tomorrow + Duration::days(days);
+            let at = |hour: u32| {
+                day.and_hms_opt(hour, 0, 0)
+                    .unwrap()
+                    .and_utc()
+                    .fixed_offset()
+            };
+            abi::Reservation::new_pending("tyrid", "room-1", at(start), at(end), "hello.")
+        };
+        let violation = |reason| abi::Error::PolicyViolation(reason);
+
+        let reserved = manager.reserve(rsvp(0, 10, 12)).await.unwrap();
+        let err = manager.reserve(rsvp(0, 7, 9)).await.unwrap_err();
+        assert_eq!(err, violation(PolicyViolationReason::OutsideOpeningHours));
+        let err = manager.reserve(rsvp(0, 12, 17)).await.unwrap_err();
+        assert_eq!(err, violation(PolicyViolationReason::TooLong));
+        let err = manager.reserve(rsvp(40, 10, 12)).await.unwrap_err();
+        assert_eq!(err, violation(PolicyViolationReason::TooFarAhead));
+
+        // rescheduling is held to the same rules
+        let (start, end) = (rsvp(0, 17, 19).start, rsvp(0, 17, 19).end);
+        let err = manager
+            .reschedule(reserved.id, start.unwrap(), end.unwrap())
+            .await
+            .unwrap_err();
+        assert_eq!(err, violation(PolicyViolationReason::OutsideOpeningHours));
+
+        // nothing is reserved if one of the reservations breaks the rules
+        let err = manager
+            .reserve_many(vec![rsvp(1, 10, 12), rsvp(1, 12, 17)])
+            .await
+            .unwrap_err();
+        assert_eq!(err, violation(PolicyViolationReason::TooLong));
+        let reservations = manager
+            .filter(abi::ReservationFilterBuilder::default().build().unwrap())
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(reservations, vec![reserved]);
+    }
+}
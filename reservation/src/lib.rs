@@ -1,10 +1,12 @@
+mod catalog;
 mod manager;
-use abi::{ReservationId, FilterPager};
+use abi::{FilterPager, ReservationId, ResourceId};
 use async_trait::async_trait;
+use prost_types::Timestamp;
 use sqlx::PgPool;
 use tokio::sync::mpsc;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ReservationManager {
     pool: PgPool,
 }
@@ -13,16 +15,102 @@ pub struct ReservationManager {
 pub trait Rsvp {
     /// make a reservation
     async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error>;
-    /// change reservation status (if current status is pending, change it to confirmed)
+    /// make several reservations in one transaction, all or nothing
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// make the reservation on a free resource of the group, tried in the order of the strategy.
+    /// resource_id of the reservation is ignored
+    async fn reserve_any(
+        &self,
+        rsvp: abi::Reservation,
+        group_id: String,
+        strategy: abi::SelectionStrategy,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// make a recurring reservation, occurrences conflicting with existing reservations are skipped,
+    /// nothing is made if every occurrence conflicts
+    async fn reserve_recurring(
+        &self,
+        rsvp: abi::Reservation,
+        rule: abi::RecurrenceRule,
+    ) -> Result<abi::ReserveRecurringResponse, abi::Error>;
+    /// confirm reservation, same as transition(id, Confirmed)
     async fn change_status(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
+    /// move reservation to another status, fail with InvalidStatusTransition if the state machine
+    /// doesn't allow it (see ReservationStatus::can_transition_to)
+    async fn transition(
+        &self,
+        id: ReservationId,
+        status: abi::ReservationStatus,
+    ) -> Result<abi::Reservation, abi::Error>;
     /// update note
     async fn update_note(
         &self,
         id: ReservationId,
         note: String,
     ) -> Result<abi::Reservation, abi::Error>;
-    /// delete reservation
+    /// move reservation to another time window, a cancelled reservation is NotFound
+    async fn reschedule(
+        &self,
+        id: ReservationId,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// move reservation to another resource, a cancelled reservation is NotFound
+    async fn transfer(
+        &self,
+        id: ReservationId,
+        resource_id: String,
+    ) -> Result<abi::Reservation, abi::Error>;
+    /// cancel reservation, it is kept with the cancelled status and no longer holds its window. the
+    /// waitlist entries fitting in the freed window are promoted to pending reservations
     async fn delete(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
+    /// cancel all the occurrences of a recurring reservation which are not cancelled yet
+    async fn cancel_series(&self, series_id: i64) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// update the note of all the occurrences of a recurring reservation if given, and move the ones
+    /// not cancelled by shift seconds. either every occurrence is updated or none
+    async fn update_series(
+        &self,
+        series_id: i64,
+        note: Option<String>,
+        shift: i64,
+    ) -> Result<Vec<abi::Reservation>, abi::Error>;
+    /// take a resource offline for all users, the policy decides what happens to the overlapping
+    /// pending reservations of the resource, its ancestors and its descendants
+    async fn blackout(
+        &self,
+        rsvp: abi::Reservation,
+        policy: abi::BlackoutPolicy,
+    ) -> Result<abi::BlackoutResponse, abi::Error>;
+    /// find the free slots of one or many resources in a window, only active resources have any
+    async fn find_free_slots(
+        &self,
+        query: abi::AvailabilityRequest,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error>;
+    /// suggest up to count nearest free windows lasting as long as the reservation, on its resource
+    /// or the sibling resources
+    async fn suggest_windows(
+        &self,
+        rsvp: abi::Reservation,
+        sibling_resource_ids: Vec<String>,
+        count: usize,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error>;
+    /// queue for a taken window of a resource, joining twice for the same window returns the
+    /// existing entry with its note updated. a free window is rejected, it should be reserved
+    async fn join_waitlist(
+        &self,
+        entry: abi::WaitlistEntry,
+    ) -> Result<abi::WaitlistEntry, abi::Error>;
+    /// remove an entry from its waitlist
+    async fn leave_waitlist(&self, id: i64) -> Result<abi::WaitlistEntry, abi::Error>;
+    /// entries of a resource overlapping the window, in the order they are promoted
+    async fn list_waitlist(
+        &self,
+        query: abi::ListWaitlistRequest,
+    ) -> Result<Vec<abi::WaitlistEntry>, abi::Error>;
+    /// cancel pending reservations whose hold has expired, they are kept with the cancelled status
+    async fn reap_expired(&self) -> Result<Vec<abi::Reservation>, abi::Error>;
     /// get reservation by id
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error>;
     /// query reservations;
@@ -34,5 +122,52 @@ pub trait Rsvp {
     async fn filter(
         &self,
         filter: abi::ReservationFilter,
-    ) -> Result<(FilterPager, Vec<abi::Reservation>), abi::Error>; 
+    ) -> Result<(FilterPager, Vec<abi::Reservation>), abi::Error>;
+    /// listen to reservation changes
+    async fn listen(
+        &self,
+        req: abi::ListenRequest,
+    ) -> mpsc::Receiver<Result<abi::ListenResponse, abi::Error>>;
+    /// acknowledge changes processed by a named consumer, return its persisted position
+    async fn ack(&self, ack: abi::AckRequest) -> Result<i64, abi::Error>;
+}
+
+/// the resource catalog, reservations can only be made on its active resources
+#[async_trait]
+pub trait Catalog {
+    /// add a resource to the catalog, it is active unless active is set to false
+    async fn create_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error>;
+    /// replace name, type, attributes, capacity, buffers and parent of a resource, and active if it
+    /// is set. the id can't be changed
+    async fn update_resource(&self, resource: abi::Resource) -> Result<abi::Resource, abi::Error>;
+    /// get resource by id
+    async fn get_resource(&self, id: ResourceId) -> Result<abi::Resource, abi::Error>;
+    /// list resources ordered by id
+    async fn list_resources(
+        &self,
+        req: abi::ListResourcesRequest,
+    ) -> Result<Vec<abi::Resource>, abi::Error>;
+    /// stop accepting reservations on a resource, existing reservations are kept
+    async fn deactivate_resource(&self, id: ResourceId) -> Result<abi::Resource, abi::Error>;
+    /// replace the booking rules of a resource, new reservations are checked against them
+    async fn set_booking_rules(
+        &self,
+        rules: abi::BookingRules,
+    ) -> Result<abi::BookingRules, abi::Error>;
+    /// get the booking rules of a resource, a resource without rules accepts any reservation
+    async fn get_booking_rules(&self, id: ResourceId) -> Result<abi::BookingRules, abi::Error>;
+    /// add a group of resources, reserve_any picks one of its members
+    async fn create_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, abi::Error>;
+    /// replace the name and the members of a group
+    async fn update_resource_group(
+        &self,
+        group: abi::ResourceGroup,
+    ) -> Result<abi::ResourceGroup, abi::Error>;
+    /// get group by id
+    async fn get_resource_group(&self, id: String) -> Result<abi::ResourceGroup, abi::Error>;
+    /// list groups ordered by id
+    async fn list_resource_groups(&self) -> Result<Vec<abi::ResourceGroup>, abi::Error>;
 }
@@ -1,15 +1,36 @@
 use crate::{ReservationId, ReservationManager, Rsvp};
-use abi::{DbConfig, FilterPager, Validator, ToSql, Normalizer};
+use abi::{
+    convert_to_timestamp, convert_to_utc_time, get_timespan, nearest_windows, validate_range,
+    DbConfig, FilterPager, Normalizer, ToSql, Validator,
+};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use futures::StreamExt;
+use prost_types::Timestamp;
 use sqlx::{
-    postgres::{types::PgRange, PgPoolOptions},
-    PgPool, Row, Either
+    postgres::{types::PgRange, PgListener, PgPoolOptions},
+    Acquire, Either, FromRow, PgExecutor, PgPool, Row,
 };
-use tokio::sync::mpsc;
+use std::{ops::Bound, time::Duration};
+use tokio::{sync::mpsc, time};
 use tracing::{info, warn};
 
+/// channel notified by rsvp.reservations_trigger() on every reservation change
+const CHANGE_CHANNEL: &str = "reservation_update";
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+/// days before and after a conflicting window to look for alternative windows in
+const SUGGESTION_HORIZON_DAYS: i64 = 7;
+const MAX_SUGGESTIONS: usize = 20;
+/// changes read per query, a listener far behind catches up in several batches
+const CHANGE_BATCH_SIZE: i64 = 500;
+
+/// (xid, id) of a change, listeners read the changes in this order
+type ChangePosition = (i64, i64);
+/// a change row decoded on its own
+type ChangeRow = Result<abi::ListenResponse, sqlx::Error>;
+/// id, user_id, status and resource_id of a reservation overlapping a rejected window
+type OverlappingRow = (i64, Option<String>, abi::RsvpStatus, String);
+
 impl ReservationManager {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
@@ -23,50 +44,305 @@ impl ReservationManager {
             .await?;
         Ok(Self::new(pool))
     }
+
+    /// fill the reservations overlapping the rejected window into a conflict error. owners are
+    /// only revealed to the requester themselves, exclude_id skips the reservation being moved
+    async fn explain_conflict(
+        &self,
+        e: abi::Error,
+        requester: &str,
+        exclude_id: ReservationId,
+    ) -> abi::Error {
+        let abi::Error::ConflictReservation(abi::ReservationConflictInfo::Parsed(mut conflict)) = e
+        else {
+            return e;
+        };
+
+        // the failed statement may have aborted its transaction, look up with a fresh connection
+        let timespan = get_timespan(
+            Some(&convert_to_timestamp(&conflict.new.start)),
+            Some(&convert_to_timestamp(&conflict.new.end)),
+        );
+        let rows: Result<Vec<OverlappingRow>, _> = sqlx::query_as(
+            "SELECT id, user_id, status, resource_id FROM rsvp.reservations \
+            WHERE (resource_id = $1 OR resource_id IN (SELECT rsvp.related_resources($1))) AND padded_timespan && $2 \
+            AND id <> $3 AND status <> 'cancelled' AND NOT flagged ORDER BY lower(timespan), id",
+        )
+        .bind(&conflict.new.rid)
+        .bind(timespan.clone())
+        .bind(exclude_id)
+        .fetch_all(&self.pool)
+        .await;
+        let saturated: Result<Vec<PgRange<DateTime<Utc>>>, _> = sqlx::query_scalar(
+            "SELECT unnest(rsvp.saturated($1, $2, $3) + rsvp.blocked_by_related($1, $2, $3))",
+        )
+        .bind(&conflict.new.rid)
+        .bind(timespan)
+        .bind(exclude_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match saturated {
+            Ok(ranges) => {
+                conflict.saturated = ranges
+                    .into_iter()
+                    .map(|range| abi::ResrvationWindow {
+                        rid: conflict.new.rid.clone(),
+                        start: bound_value(range.start).unwrap_or(DateTime::<Utc>::MIN_UTC),
+                        end: bound_value(range.end).unwrap_or(DateTime::<Utc>::MAX_UTC),
+                    })
+                    .collect();
+            }
+            Err(e) => warn!("failed to look up saturated windows: {}", e),
+        }
+        match rows {
+            Ok(rows) => {
+                conflict.existing = rows
+                    .into_iter()
+                    .map(
+                        |(id, user_id, status, resource_id)| abi::ConflictingReservation {
+                            id,
+                            user_id: user_id
+                                .filter(|v| !requester.is_empty() && v == requester)
+                                .unwrap_or_default(),
+                            status: abi::ReservationStatus::from(status) as i32,
+                            resource_id,
+                        },
+                    )
+                    .collect();
+            }
+            Err(e) => warn!("failed to look up conflicting reservations: {}", e),
+        }
+        abi::Error::ConflictReservation(abi::ReservationConflictInfo::Parsed(conflict))
+    }
 }
 
 #[async_trait]
 impl Rsvp for ReservationManager {
-    async fn reserve(&self, mut rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error> {
+    async fn reserve(&self, rsvp: abi::Reservation) -> Result<abi::Reservation, abi::Error> {
         rsvp.validate()?;
+        check_booking_rules(&self.pool, std::slice::from_ref(&rsvp)).await?;
+        let requester = rsvp.user_id.clone();
+        match insert_reservation(&self.pool, rsvp, None).await {
+            Err(e) => Err(self.explain_conflict(e, &requester, 0).await),
+            reserved => reserved,
+        }
+    }
+
+    async fn reserve_many(
+        &self,
+        rsvps: Vec<abi::Reservation>,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        if rsvps.is_empty() {
+            return Err(abi::Error::EmptyBatch);
+        }
+        // validate everything before touching the database
+        for rsvp in &rsvps {
+            rsvp.validate()?;
+        }
+        check_booking_rules(&self.pool, &rsvps).await?;
 
-        let status = abi::ReservationStatus::from_i32(rsvp.status)
-            .unwrap_or(abi::ReservationStatus::Pending);
+        // the transaction is rolled back on drop if any reservation fails
+        let mut tx = self.pool.begin().await?;
+        let mut reserved = Vec::with_capacity(rsvps.len());
+        for (index, rsvp) in rsvps.into_iter().enumerate() {
+            let requester = rsvp.user_id.clone();
+            match insert_reservation(&mut *tx, rsvp, None).await {
+                Ok(rsvp) => reserved.push(rsvp),
+                Err(e) => {
+                    drop(tx);
+                    return Err(match self.explain_conflict(e, &requester, 0).await {
+                        abi::Error::ConflictReservation(info) => {
+                            abi::Error::BatchConflict(index, info)
+                        }
+                        e => e,
+                    });
+                }
+            }
+        }
+        tx.commit().await?;
 
-        let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan();
-        // generate a insert sql for the reservation
-        // execute the sql
-        // Postgre对类型要求严格
+        Ok(reserved)
+    }
 
-        // println!("{}, {}, {}, {}, {}", rsvp.user_id, rsvp.resource_id, timespan, rsvp.note, status.to_string());
-        let id = sqlx::query(
-            "INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status) VALUES ($1, $2, $3, $4, $5::rsvp.reservation_status) RETURNING id"
-        )
-        .bind(rsvp.user_id.clone())
-        .bind(rsvp.resource_id.clone())
-        .bind(timespan)
-        .bind(rsvp.note.clone())
-        .bind(status.to_string())
-        .fetch_one(&self.pool)
-        .await?.get(0);
+    async fn reserve_any(
+        &self,
+        rsvp: abi::Reservation,
+        group_id: String,
+        strategy: abi::SelectionStrategy,
+    ) -> Result<abi::Reservation, abi::Error> {
+        if group_id.is_empty() {
+            return Err(abi::Error::InvalidResourceGroup("empty id".into()));
+        }
+
+        let order = match strategy {
+            abi::SelectionStrategy::FirstFit => "m.position",
+            // fewest upcoming reservations first
+            abi::SelectionStrategy::LeastUsed => {
+                "(SELECT count(*) FROM rsvp.reservations r WHERE r.resource_id = m.resource_id \
+                AND r.status <> 'cancelled' AND r.user_id IS NOT NULL AND upper(r.timespan) > now()), \
+                m.position"
+            }
+            // members after the last picked one first, then wrap around
+            abi::SelectionStrategy::RoundRobin => {
+                "m.position <= COALESCE((SELECT l.position FROM rsvp.resource_group_members l \
+                WHERE l.group_id = m.group_id AND l.resource_id = g.last_resource_id), 0), \
+                m.position"
+            }
+        };
 
-        // println!("{:?}", rsvp);
+        // the group row is locked so concurrent round-robin picks see each other
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT id FROM rsvp.resource_groups WHERE id = $1 FOR UPDATE")
+            .bind(&group_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(abi::Error::NotFound)?;
+        let candidates: Vec<String> = sqlx::query_scalar(&format!(
+            "SELECT m.resource_id FROM rsvp.resource_group_members m \
+            JOIN rsvp.resource_groups g ON g.id = m.group_id WHERE m.group_id = $1 ORDER BY {}",
+            order
+        ))
+        .bind(&group_id)
+        .fetch_all(&mut *tx)
+        .await?;
 
-        rsvp.id = id;
+        for resource_id in candidates {
+            let candidate = abi::Reservation {
+                resource_id,
+                ..rsvp.clone()
+            };
+            // the reservation is validated once it has a resource, like reserve does
+            candidate.validate()?;
+            // checked on the transaction, waiting for a second connection while holding the group
+            // lock could exhaust the pool
+            match check_booking_rules(&mut *tx, std::slice::from_ref(&candidate)).await {
+                Err(abi::Error::PolicyViolation(_)) => continue,
+                checked => checked?,
+            }
 
-        Ok(rsvp)
+            // a busy or inactive member only rolls back its own savepoint
+            let mut savepoint = tx.begin().await?;
+            match insert_reservation(&mut *savepoint, candidate, None).await {
+                Ok(reserved) => {
+                    savepoint.commit().await?;
+                    sqlx::query(
+                        "UPDATE rsvp.resource_groups SET last_resource_id = $1 WHERE id = $2",
+                    )
+                    .bind(&reserved.resource_id)
+                    .bind(&group_id)
+                    .execute(&mut *tx)
+                    .await?;
+                    tx.commit().await?;
+                    return Ok(reserved);
+                }
+                Err(abi::Error::ConflictReservation(_) | abi::Error::UnknownResource(_)) => {
+                    savepoint.rollback().await?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(abi::Error::NoAvailableResource(group_id))
+    }
+
+    async fn reserve_recurring(
+        &self,
+        rsvp: abi::Reservation,
+        rule: abi::RecurrenceRule,
+    ) -> Result<abi::ReserveRecurringResponse, abi::Error> {
+        rsvp.validate()?;
+        let start = convert_to_utc_time(rsvp.start.as_ref().unwrap());
+        let end = convert_to_utc_time(rsvp.end.as_ref().unwrap());
+        let occurrences: Vec<_> = rule
+            .expand(start, end)?
+            .into_iter()
+            .map(|(start, end)| abi::Reservation {
+                start: Some(convert_to_timestamp(&start)),
+                end: Some(convert_to_timestamp(&end)),
+                ..rsvp.clone()
+            })
+            .collect();
+        // an occurrence breaking the booking rules fails the whole series
+        check_booking_rules(&self.pool, &occurrences).await?;
+
+        let mut tx = self.pool.begin().await?;
+        let series_id: i64 = sqlx::query_scalar("SELECT nextval('rsvp.reservation_series_id_seq')")
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let mut reservations = Vec::with_capacity(occurrences.len());
+        let mut conflicts = vec![];
+        let mut conflict = None;
+        for occurrence in occurrences {
+            // every occurrence gets its own savepoint, a conflict only rolls back that occurrence
+            let mut savepoint = tx.begin().await?;
+            match insert_reservation(&mut *savepoint, occurrence.clone(), Some(series_id)).await {
+                Ok(reserved) => {
+                    savepoint.commit().await?;
+                    reservations.push(reserved);
+                }
+                Err(e @ abi::Error::ConflictReservation(_)) => {
+                    savepoint.rollback().await?;
+                    conflicts.push(occurrence);
+                    conflict.get_or_insert(e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        // a series without any occurrence is not made, report the conflict of the first one instead
+        if let (true, Some(e)) = (reservations.is_empty(), conflict) {
+            drop(tx);
+            return Err(self.explain_conflict(e, &rsvp.user_id, 0).await);
+        }
+        tx.commit().await?;
+
+        Ok(abi::ReserveRecurringResponse {
+            series_id,
+            reservations,
+            conflicts,
+        })
     }
 
     async fn change_status(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error> {
-        // if current status is pending, change it to confirmed , otherwise do nothing
-        if id == 0 {
-            return Err(abi::Error::InvalidReservationId(id));
+        self.transition(id, abi::ReservationStatus::Confirmed).await
+    }
+
+    async fn transition(
+        &self,
+        id: ReservationId,
+        status: abi::ReservationStatus,
+    ) -> Result<abi::Reservation, abi::Error> {
+        id.validate()?;
+        // lock the row, so the transition is checked against the status it is applied to
+        let mut tx = self.pool.begin().await?;
+        let rsvp: abi::Reservation =
+            sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = $1 FOR UPDATE")
+                .bind(id)
+                .fetch_one(&mut *tx)
+                .await?;
+
+        let current = rsvp.status();
+        // blackouts stay blocked until they are cancelled
+        let blackout_released =
+            rsvp.is_blackout() && status != current && status != abi::ReservationStatus::Cancelled;
+        if !current.can_transition_to(status) || blackout_released {
+            return Err(abi::Error::InvalidStatusTransition(current, status));
+        }
+        if current == status {
+            return Ok(rsvp);
         }
-        // let id = Uuid::parse_str(&id).map_err(|_| abi::Error::InvalidReservationId(id.clone()))?;
-        let rsvp: abi::Reservation = sqlx::query_as("UPDATE rsvp.reservations SET status = 'confirmed' WHERE id = $1 AND status = 'pending' RETURNING *")
-            .bind(id)
-            .fetch_one(&self.pool)
-            .await?;
+
+        // only pending reservations are on hold. a flagged reservation takes its window back, which
+        // fails if the blackout is still there
+        let rsvp = sqlx::query_as(
+            "UPDATE rsvp.reservations SET status = $1::rsvp.reservation_status, hold_expires_at = NULL, flagged = FALSE WHERE id = $2 RETURNING *",
+        )
+        .bind(status.to_string())
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
 
         Ok(rsvp)
     }
@@ -89,6 +365,383 @@ impl Rsvp for ReservationManager {
         Ok(rsvp)
     }
 
+    async fn reschedule(
+        &self,
+        id: ReservationId,
+        start: Timestamp,
+        end: Timestamp,
+    ) -> Result<abi::Reservation, abi::Error> {
+        // move the reservation in a single statement, so the slot is never released in between
+        id.validate()?;
+        validate_range(Some(&start), Some(&end))?;
+        let mut tx = self.pool.begin().await?;
+        let moved = abi::Reservation {
+            start: Some(start.clone()),
+            end: Some(end.clone()),
+            ..lock_active_reservation(&mut *tx, id).await?
+        };
+        check_booking_rules(&mut *tx, &[moved]).await?;
+        let timespan = get_timespan(Some(&start), Some(&end));
+        let rsvp = sqlx::query_as(
+            "UPDATE rsvp.reservations SET timespan = $1, flagged = FALSE WHERE id = $2 RETURNING *",
+        )
+        .bind(timespan)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await;
+
+        match rsvp {
+            Ok(rsvp) => {
+                tx.commit().await?;
+                Ok(rsvp)
+            }
+            Err(e) => {
+                drop(tx);
+                Err(self.explain_conflict(e.into(), "", id).await)
+            }
+        }
+    }
+
+    async fn transfer(
+        &self,
+        id: ReservationId,
+        resource_id: String,
+    ) -> Result<abi::Reservation, abi::Error> {
+        // reservations_conflict rejects the move if the window is taken on the new resource
+        id.validate()?;
+        if resource_id.is_empty() {
+            return Err(abi::Error::InvalidResourceId(resource_id));
+        }
+        let mut tx = self.pool.begin().await?;
+        let moved = abi::Reservation {
+            resource_id: resource_id.clone(),
+            ..lock_active_reservation(&mut *tx, id).await?
+        };
+        check_booking_rules(&mut *tx, &[moved]).await?;
+        let rsvp = sqlx::query_as(
+            "UPDATE rsvp.reservations SET resource_id = $1, flagged = FALSE WHERE id = $2 \
+            AND $1 IN (SELECT id FROM rsvp.resources WHERE active) RETURNING *",
+        )
+        .bind(&resource_id)
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await;
+
+        match rsvp {
+            Ok(rsvp) => {
+                tx.commit().await?;
+                Ok(rsvp)
+            }
+            // the reservation is locked above, so the new resource is unknown or inactive
+            Err(sqlx::Error::RowNotFound) => Err(abi::Error::UnknownResource(resource_id)),
+            Err(e) => {
+                drop(tx);
+                Err(self.explain_conflict(e.into(), "", id).await)
+            }
+        }
+    }
+
+    async fn cancel_series(&self, series_id: i64) -> Result<Vec<abi::Reservation>, abi::Error> {
+        if series_id <= 0 {
+            return Err(abi::Error::InvalidSeriesId(series_id));
+        }
+        let rsvps: Vec<abi::Reservation> = sqlx::query_as(
+            "WITH cancelled AS (UPDATE rsvp.reservations SET status = 'cancelled', hold_expires_at = NULL, flagged = FALSE \
+            WHERE series_id = $1 AND status <> 'cancelled' RETURNING *) \
+            SELECT * FROM cancelled ORDER BY lower(timespan)",
+        )
+        .bind(series_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        if rsvps.is_empty() {
+            return Err(abi::Error::NotFound);
+        }
+        Ok(rsvps)
+    }
+
+    async fn update_series(
+        &self,
+        series_id: i64,
+        note: Option<String>,
+        shift: i64,
+    ) -> Result<Vec<abi::Reservation>, abi::Error> {
+        if series_id <= 0 {
+            return Err(abi::Error::InvalidSeriesId(series_id));
+        }
+        let shift = chrono::Duration::seconds(shift);
+        let mut moved = vec![];
+        if !shift.is_zero() {
+            let occurrences: Vec<abi::Reservation> = sqlx::query_as(
+                "SELECT * FROM rsvp.reservations WHERE series_id = $1 AND status <> 'cancelled' \
+                ORDER BY lower(timespan)",
+            )
+            .bind(series_id)
+            .fetch_all(&self.pool)
+            .await?;
+            moved = occurrences
+                .into_iter()
+                .map(|rsvp| abi::Reservation {
+                    start: rsvp
+                        .start
+                        .as_ref()
+                        .map(|v| convert_to_timestamp(&(convert_to_utc_time(v) + shift))),
+                    end: rsvp
+                        .end
+                        .as_ref()
+                        .map(|v| convert_to_timestamp(&(convert_to_utc_time(v) + shift))),
+                    ..rsvp
+                })
+                .collect();
+            // moving forward, the last occurrence goes first so none lands on one not moved yet
+            if shift > chrono::Duration::zero() {
+                moved.reverse();
+            }
+            check_booking_rules(&self.pool, &moved).await?;
+        }
+
+        // either every occurrence is updated or none
+        let mut tx = self.pool.begin().await?;
+        if let Some(note) = note {
+            sqlx::query("UPDATE rsvp.reservations SET note = $1 WHERE series_id = $2")
+                .bind(note)
+                .bind(series_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+        for rsvp in &moved {
+            let result = sqlx::query(
+                "UPDATE rsvp.reservations SET timespan = $1, flagged = FALSE WHERE id = $2",
+            )
+            .bind(rsvp.get_timespan())
+            .bind(rsvp.id)
+            .execute(&mut *tx)
+            .await;
+            if let Err(e) = result {
+                drop(tx);
+                return Err(self.explain_conflict(e.into(), "", rsvp.id).await);
+            }
+        }
+        let rsvps: Vec<abi::Reservation> = sqlx::query_as(
+            "SELECT * FROM rsvp.reservations WHERE series_id = $1 ORDER BY lower(timespan)",
+        )
+        .bind(series_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        if rsvps.is_empty() {
+            return Err(abi::Error::NotFound);
+        }
+        tx.commit().await?;
+        Ok(rsvps)
+    }
+
+    async fn blackout(
+        &self,
+        rsvp: abi::Reservation,
+        policy: abi::BlackoutPolicy,
+    ) -> Result<abi::BlackoutResponse, abi::Error> {
+        if !rsvp.user_id.is_empty() {
+            return Err(abi::Error::InvalidUserId(rsvp.user_id));
+        }
+        if rsvp.resource_id.is_empty() {
+            return Err(abi::Error::InvalidResourceId(rsvp.resource_id));
+        }
+        validate_range(rsvp.start.as_ref(), rsvp.end.as_ref())?;
+
+        let timespan = rsvp.get_timespan();
+        let mut tx = self.pool.begin().await?;
+        // the blackout is flagged, not holding its window, while the policy makes room for it. the
+        // waitlist is not promoted into it meanwhile, see rsvp.reservations_waitlist()
+        let blackout: abi::Reservation = sqlx::query_as(
+            "INSERT INTO rsvp.reservations (resource_id, timespan, note, status, flagged) \
+            VALUES ($1, $2, $3, 'blocked', TRUE) RETURNING *",
+        )
+        .bind(&rsvp.resource_id)
+        .bind(timespan.clone())
+        .bind(rsvp.note)
+        .fetch_one(&mut *tx)
+        .await?;
+        let affected: Vec<abi::Reservation> = match policy {
+            abi::BlackoutPolicy::Reject => vec![],
+            abi::BlackoutPolicy::CancelPending => {
+                sqlx::query_as(
+                    "UPDATE rsvp.reservations SET status = 'cancelled', hold_expires_at = NULL, flagged = FALSE \
+                    WHERE (resource_id = $1 OR resource_id IN (SELECT rsvp.related_resources($1))) AND padded_timespan && $2 \
+                    AND status = 'pending' AND user_id IS NOT NULL RETURNING *",
+                )
+                .bind(&rsvp.resource_id)
+                .bind(timespan.clone())
+                .fetch_all(&mut *tx)
+                .await?
+            }
+            abi::BlackoutPolicy::FlagPending => {
+                sqlx::query_as(
+                    "UPDATE rsvp.reservations SET flagged = TRUE \
+                    WHERE (resource_id = $1 OR resource_id IN (SELECT rsvp.related_resources($1))) AND padded_timespan && $2 \
+                    AND status = 'pending' AND user_id IS NOT NULL AND NOT flagged RETURNING *",
+                )
+                .bind(&rsvp.resource_id)
+                .bind(timespan)
+                .fetch_all(&mut *tx)
+                .await?
+            }
+        };
+
+        // anything still overlapping fails here
+        let blackout = sqlx::query_as(
+            "UPDATE rsvp.reservations SET flagged = FALSE WHERE id = $1 RETURNING *",
+        )
+        .bind(blackout.id)
+        .fetch_one(&mut *tx)
+        .await;
+        let blackout = match blackout {
+            Ok(blackout) => blackout,
+            Err(e) => {
+                drop(tx);
+                return Err(self.explain_conflict(e.into(), "", 0).await);
+            }
+        };
+        tx.commit().await?;
+
+        Ok(abi::BlackoutResponse {
+            blackout: Some(blackout),
+            affected,
+        })
+    }
+
+    async fn find_free_slots(
+        &self,
+        query: abi::AvailabilityRequest,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error> {
+        query.validate()?;
+        let slots = query
+            .to_sql()
+            .build_query_as()
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(slots)
+    }
+
+    async fn suggest_windows(
+        &self,
+        rsvp: abi::Reservation,
+        sibling_resource_ids: Vec<String>,
+        count: usize,
+    ) -> Result<Vec<abi::FreeSlot>, abi::Error> {
+        rsvp.validate()?;
+        let start = convert_to_utc_time(rsvp.start.as_ref().unwrap());
+        let end = convert_to_utc_time(rsvp.end.as_ref().unwrap());
+        let horizon = chrono::Duration::days(SUGGESTION_HORIZON_DAYS);
+
+        let mut resource_ids = vec![rsvp.resource_id];
+        for rid in sibling_resource_ids {
+            if !resource_ids.contains(&rid) {
+                resource_ids.push(rid);
+            }
+        }
+        let query = abi::AvailabilityRequest {
+            resource_ids,
+            start: Some(convert_to_timestamp(&(start - horizon))),
+            end: Some(convert_to_timestamp(&(end + horizon))),
+            min_duration: (end - start).num_seconds(),
+        };
+        let slots = self.find_free_slots(query).await?;
+
+        Ok(nearest_windows(
+            slots,
+            start,
+            end,
+            count.min(MAX_SUGGESTIONS),
+        ))
+    }
+
+    async fn join_waitlist(
+        &self,
+        entry: abi::WaitlistEntry,
+    ) -> Result<abi::WaitlistEntry, abi::Error> {
+        entry.validate()?;
+        // the entry is promoted without asking again, it has to follow the rules already
+        check_booking_rules(&self.pool, std::slice::from_ref(&entry.to_reservation())).await?;
+
+        // only a taken window is waited for: padded with the buffers, it overlaps a window in which
+        // the resource is fully booked or blocked by a related resource
+        let taken: bool = sqlx::query_scalar(
+            "SELECT NOT isempty(rsvp.saturated(id, p.padded) + rsvp.blocked_by_related(id, p.padded)) \
+            FROM rsvp.resources CROSS JOIN LATERAL (SELECT tstzrange(lower($2) - buffer_before * interval '1 second', \
+            upper($2) + buffer_after * interval '1 second') AS padded) AS p WHERE id = $1 AND active",
+        )
+        .bind(&entry.resource_id)
+        .bind(entry.get_timespan())
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| abi::Error::UnknownResource(entry.resource_id.clone()))?;
+        if !taken {
+            return Err(abi::Error::WindowNotTaken(entry.resource_id));
+        }
+
+        let joined = sqlx::query_as(
+            "INSERT INTO rsvp.waitlist (user_id, resource_id, timespan, note) \
+            SELECT $1, id, $3, $4 FROM rsvp.resources WHERE id = $2 AND active \
+            ON CONFLICT (user_id, resource_id, timespan) DO UPDATE SET note = EXCLUDED.note RETURNING *",
+        )
+        .bind(&entry.user_id)
+        .bind(&entry.resource_id)
+        .bind(entry.get_timespan())
+        .bind(&entry.note)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        joined.ok_or(abi::Error::UnknownResource(entry.resource_id))
+    }
+
+    async fn leave_waitlist(&self, id: i64) -> Result<abi::WaitlistEntry, abi::Error> {
+        let entry = sqlx::query_as("DELETE FROM rsvp.waitlist WHERE id = $1 RETURNING *")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(entry)
+    }
+
+    async fn list_waitlist(
+        &self,
+        query: abi::ListWaitlistRequest,
+    ) -> Result<Vec<abi::WaitlistEntry>, abi::Error> {
+        if query.resource_id.is_empty() {
+            return Err(abi::Error::InvalidResourceId(query.resource_id));
+        }
+        let start = query.start.as_ref().map(convert_to_utc_time);
+        let end = query.end.as_ref().map(convert_to_utc_time);
+        if matches!((start, end), (Some(start), Some(end)) if start >= end) {
+            return Err(abi::Error::InvalidTime);
+        }
+
+        let entries = sqlx::query_as(
+            "SELECT * FROM rsvp.waitlist WHERE resource_id = $1 AND timespan && tstzrange($2, $3) ORDER BY id",
+        )
+        .bind(&query.resource_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    async fn reap_expired(&self) -> Result<Vec<abi::Reservation>, abi::Error> {
+        // reaped reservations are cancelled and kept for history like any other, hold_expires_at
+        // tells why. the trigger records a delete change for every one of them
+        let rsvps = sqlx::query_as(
+            "UPDATE rsvp.reservations SET status = 'cancelled', flagged = FALSE \
+            WHERE status = 'pending' AND hold_expires_at <= now() RETURNING *",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rsvps)
+    }
+
     async fn get(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error> {
         // get the reservation by id
         id.validate()?;
@@ -103,15 +756,8 @@ impl Rsvp for ReservationManager {
     }
 
     async fn delete(&self, id: ReservationId) -> Result<abi::Reservation, abi::Error> {
-        // delete the reservation by id
-        id.validate()?;
-        // let id = Uuid::parse_str(&id).map_err(|_| abi::Error::InvalidReservationId(id.clone()))?;
-        let rsvp: abi::Reservation =
-            sqlx::query_as("DELETE FROM rsvp.reservations WHERE id = $1 RETURNING *")
-            .bind(id)
-            .fetch_one(&self.pool)
-            .await?;
-        Ok(rsvp)
+        // cancelled reservations are kept for history, they no longer hold their window
+        self.transition(id, abi::ReservationStatus::Cancelled).await
     }
 
     async fn query(
@@ -129,8 +775,8 @@ impl Rsvp for ReservationManager {
         let (tx, rx) = mpsc::channel(128);
 
         tokio::spawn(async move {
-            let sql = query.to_sql();
-            let mut rsvps = sqlx::query_as(&sql).fetch_many(&pool);
+            let mut sql = query.to_sql();
+            let mut rsvps = sql.build_query_as().fetch_many(&pool);
             while let Some(ret) = rsvps.next().await {
                 match ret {
                     Ok(Either::Left(r)) => {
@@ -191,16 +837,311 @@ impl Rsvp for ReservationManager {
     ) -> Result<(FilterPager, Vec<abi::Reservation>), abi::Error> {
         filter.normalize()?;
         // normalize 中的 validate 用于验证 filter 本身是ok的
-        let sql = filter.to_sql();
-        let rsvps: Vec<abi::Reservation> = sqlx::query_as(&sql)
-            .fetch_all(&self.pool)
-            .await?;
+        let mut sql = filter.to_sql();
+        let rsvps: Vec<abi::Reservation> = sql.build_query_as().fetch_all(&self.pool).await?;
 
         let mut rsvps = rsvps.into_iter().collect();
 
         let pager = filter.get_pager(&mut rsvps);
         Ok((pager, rsvps.into_iter().collect()))
     }
+
+    /// listen to reservation changes (create/update/delete), see abi::ListenRequest for where
+    /// the stream starts
+    async fn listen(
+        &self,
+        req: abi::ListenRequest,
+    ) -> mpsc::Receiver<Result<abi::ListenResponse, abi::Error>> {
+        let pool = self.pool.clone();
+        let (tx, rx) = mpsc::channel(128);
+
+        // subscribe before reading the start position, so nothing committed in between is lost
+        let setup = async {
+            req.validate()?;
+            let mut listener = PgListener::connect_with(&pool).await?;
+            listener.listen(CHANGE_CHANNEL).await?;
+            let position = match req.start_id {
+                Some(start_id) => position_before(&pool, start_id).await?,
+                None => resume_position(&pool, &req.consumer_id).await?,
+            };
+            Ok::<_, abi::Error>((listener, position))
+        };
+
+        let (mut listener, mut position) = match setup.await {
+            Ok(v) => v,
+            Err(e) => {
+                // the receiver is returned right below, so the error can be buffered
+                let _ = tx.send(Err(e)).await;
+                return rx;
+            }
+        };
+
+        tokio::spawn(async move {
+            loop {
+                // catch up with every change after position, a notification may stand for many rows
+                let (pending, full) = match fetch_changes(&pool, position).await {
+                    Ok((changes, pending)) => {
+                        let full = changes.len() as i64 == CHANGE_BATCH_SIZE;
+                        for (change_position, change) in changes {
+                            position = change_position;
+                            let change_id = change_position.1;
+                            match change {
+                                Ok(change) => {
+                                    if tx.send(Ok(change)).await.is_err() {
+                                        // rx is dropped, so client disconnected
+                                        return;
+                                    }
+                                }
+                                // retrying a row which can't be decoded would stall the stream
+                                Err(e) => {
+                                    warn!("Skip reservation change {}: {:?}", change_id, e)
+                                }
+                            }
+                        }
+                        (pending, full)
+                    }
+                    Err(e) => {
+                        warn!("Fetch reservation changes error: {:?}", e);
+                        time::sleep(RETRY_INTERVAL).await;
+                        continue;
+                    }
+                };
+                // a full batch may be followed by more, read on until the backlog is drained
+                if full {
+                    continue;
+                }
+
+                tokio::select! {
+                    // Ok(None) means the connection was lost and will be re-established on the
+                    // next call, either way we go back and fetch what we might have missed
+                    ret = listener.try_recv() => {
+                        if let Err(e) = ret {
+                            warn!("Listen reservation changes error: {:?}", e);
+                            time::sleep(RETRY_INTERVAL).await;
+                        }
+                    }
+                    // changes held back behind a running transaction get no notification when it
+                    // ends, unless it changed reservations as well
+                    _ = time::sleep(RETRY_INTERVAL), if pending => {}
+                    _ = tx.closed() => return,
+                }
+            }
+        });
+
+        rx
+    }
+
+    /// persist the position a named consumer has processed, it never moves backwards
+    async fn ack(&self, ack: abi::AckRequest) -> Result<i64, abi::Error> {
+        ack.validate()?;
+        let change_id = sqlx::query_scalar(
+            "INSERT INTO rsvp.server_read_cursor (server_id, last_change_id) VALUES ($1, $2) \
+            ON CONFLICT (server_id) DO UPDATE \
+            SET last_change_id = GREATEST(rsvp.server_read_cursor.last_change_id, EXCLUDED.last_change_id) \
+            RETURNING last_change_id",
+        )
+        .bind(ack.consumer_id)
+        .bind(ack.change_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(change_id)
+    }
+}
+
+/// check valid reservations against the booking rules of their resources
+async fn check_booking_rules<'e>(
+    executor: impl PgExecutor<'e>,
+    rsvps: &[abi::Reservation],
+) -> Result<(), abi::Error> {
+    let resource_ids: Vec<&str> = rsvps.iter().map(|r| r.resource_id.as_str()).collect();
+    let rules: Vec<abi::BookingRules> =
+        sqlx::query_as("SELECT * FROM rsvp.booking_rules WHERE resource_id = ANY($1)")
+            .bind(resource_ids)
+            .fetch_all(executor)
+            .await?;
+
+    let now = Utc::now();
+    for rsvp in rsvps {
+        if let Some(rules) = rules.iter().find(|r| r.resource_id == rsvp.resource_id) {
+            rules.check(rsvp, now)?;
+        }
+    }
+    Ok(())
+}
+
+/// lock a reservation to move it, a cancelled reservation is gone and can't be moved anymore
+async fn lock_active_reservation<'e>(
+    executor: impl PgExecutor<'e>,
+    id: ReservationId,
+) -> Result<abi::Reservation, abi::Error> {
+    let rsvp: abi::Reservation =
+        sqlx::query_as("SELECT * FROM rsvp.reservations WHERE id = $1 FOR UPDATE")
+            .bind(id)
+            .fetch_one(executor)
+            .await?;
+    if rsvp.status() == abi::ReservationStatus::Cancelled {
+        return Err(abi::Error::NotFound);
+    }
+    Ok(rsvp)
+}
+
+// the reservation is validated by the callers, it always starts pending
+async fn insert_reservation<'e>(
+    executor: impl PgExecutor<'e>,
+    mut rsvp: abi::Reservation,
+    series_id: Option<i64>,
+) -> Result<abi::Reservation, abi::Error> {
+    let timespan: PgRange<DateTime<Utc>> = rsvp.get_timespan();
+    // generate a insert sql for the reservation
+    // execute the sql
+    // Postgre对类型要求严格
+
+    // println!("{}, {}, {}, {}, {}", rsvp.user_id, rsvp.resource_id, timespan, rsvp.note, status.to_string());
+    // check the resource in the catalog within the insert, only active resources can be reserved
+    let (active, id, padded): (Option<bool>, Option<i64>, Option<PgRange<DateTime<Utc>>>) = sqlx::query_as(
+        "WITH resource AS (SELECT active FROM rsvp.resources WHERE id = $2), \
+        inserted AS (INSERT INTO rsvp.reservations (user_id, resource_id, timespan, note, status, series_id, hold_expires_at) \
+        SELECT $1, $2, $3, $4, 'pending', $5, $6 FROM resource WHERE active RETURNING id, padded_timespan) \
+        SELECT (SELECT active FROM resource), (SELECT id FROM inserted), (SELECT padded_timespan FROM inserted)"
+    )
+    .bind(rsvp.user_id.clone())
+    .bind(rsvp.resource_id.clone())
+    .bind(timespan)
+    .bind(rsvp.note.clone())
+    .bind(series_id)
+    .bind(rsvp.hold_expires_at.as_ref().map(convert_to_utc_time))
+    .fetch_one(executor)
+    .await?;
+
+    // println!("{:?}", rsvp);
+    let (Some(id), Some(padded)) = (id.filter(|_| active == Some(true)), padded) else {
+        return Err(abi::Error::UnknownResource(rsvp.resource_id));
+    };
+
+    rsvp.id = id;
+    rsvp.status = abi::ReservationStatus::Pending as i32;
+    rsvp.set_blocked_timespan(padded);
+    rsvp.series_id = series_id.unwrap_or_default();
+
+    Ok(rsvp)
+}
+
+fn bound_value<T>(bound: Bound<T>) -> Option<T> {
+    match bound {
+        Bound::Included(v) | Bound::Excluded(v) => Some(v),
+        Bound::Unbounded => None,
+    }
+}
+
+/// position of the last change a listener without start_id has already seen: the acknowledged
+/// position of the consumer, or the latest change for anonymous and new consumers
+async fn resume_position(pool: &PgPool, consumer_id: &str) -> Result<ChangePosition, sqlx::Error> {
+    if !consumer_id.is_empty() {
+        let acked: Option<i64> = sqlx::query_scalar(
+            "SELECT last_change_id FROM rsvp.server_read_cursor WHERE server_id = $1",
+        )
+        .bind(consumer_id)
+        .fetch_optional(pool)
+        .await?;
+        if let Some(id) = acked {
+            return position_after(pool, id).await;
+        }
+    }
+
+    let latest = latest_position(pool).await?;
+
+    if !consumer_id.is_empty() {
+        // remember where a new consumer starts, so a restart before its first ack loses nothing
+        sqlx::query(
+            "INSERT INTO rsvp.server_read_cursor (server_id, last_change_id) VALUES ($1, $2) \
+            ON CONFLICT (server_id) DO NOTHING",
+        )
+        .bind(consumer_id)
+        .bind(latest.1)
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(latest)
+}
+
+/// position of the change with the given id, as if every change before it was seen
+async fn position_after(pool: &PgPool, change_id: i64) -> Result<ChangePosition, sqlx::Error> {
+    let position = sqlx::query_as(
+        "SELECT xid::text::bigint, id::bigint FROM rsvp.reservation_changes WHERE id = $1",
+    )
+    .bind(change_id)
+    .fetch_optional(pool)
+    .await?;
+    match position {
+        Some(position) => Ok(position),
+        None => position_before(pool, change_id + 1).await,
+    }
+}
+
+/// position from which the change with the given id and every later id are read again, changes
+/// of later transactions with a lower id are replayed as well
+async fn position_before(pool: &PgPool, change_id: i64) -> Result<ChangePosition, sqlx::Error> {
+    let xid: Option<i64> = sqlx::query_scalar(
+        "SELECT xid::text::bigint FROM rsvp.reservation_changes WHERE id >= $1 ORDER BY xid LIMIT 1",
+    )
+    .bind(change_id)
+    .fetch_optional(pool)
+    .await?;
+    match xid {
+        // change ids start from 1, every change of the transaction is after (xid, 0)
+        Some(xid) => Ok((xid, 0)),
+        None => latest_position(pool).await,
+    }
+}
+
+/// position of the last change readable now, the changes still to come are all after it
+async fn latest_position(pool: &PgPool) -> Result<ChangePosition, sqlx::Error> {
+    let position = sqlx::query_as(
+        "SELECT xid::text::bigint, id::bigint FROM rsvp.reservation_changes \
+        WHERE xid < pg_snapshot_xmin(pg_current_snapshot()) ORDER BY xid DESC, id DESC LIMIT 1",
+    )
+    .fetch_optional(pool)
+    .await?;
+    Ok(position.unwrap_or((0, 0)))
+}
+
+/// at most CHANGE_BATCH_SIZE changes after position, each decoded on its own so a bad row can be
+/// skipped. a change is only read once every transaction which could still add one before it has
+/// ended, the flag tells whether some changes are held back
+async fn fetch_changes(
+    pool: &PgPool,
+    position: ChangePosition,
+) -> Result<(Vec<(ChangePosition, ChangeRow)>, bool), sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id::bigint AS change_id, xid::text::bigint AS xid, op, old, new, \
+        lower((old->>'timespan')::tstzrange) AS old_start, upper((old->>'timespan')::tstzrange) AS old_end, \
+        lower((old->>'padded_timespan')::tstzrange) AS old_blocked_start, \
+        upper((old->>'padded_timespan')::tstzrange) AS old_blocked_end, \
+        lower((new->>'timespan')::tstzrange) AS new_start, upper((new->>'timespan')::tstzrange) AS new_end, \
+        lower((new->>'padded_timespan')::tstzrange) AS new_blocked_start, \
+        upper((new->>'padded_timespan')::tstzrange) AS new_blocked_end, \
+        xid < pg_snapshot_xmin(pg_current_snapshot()) AS settled FROM rsvp.reservation_changes \
+        WHERE (xid, id) > ($1::text::xid8, $2) ORDER BY xid, id LIMIT $3",
+    )
+    .bind(position.0.to_string())
+    .bind(position.1)
+    .bind(CHANGE_BATCH_SIZE)
+    .fetch_all(pool)
+    .await?;
+
+    // settled rows come first, their xid is below the xid of any running transaction
+    let changes: Vec<_> = rows
+        .iter()
+        .take_while(|row| row.get::<bool, _>("settled"))
+        .map(|row| {
+            let position = (row.get("xid"), row.get("change_id"));
+            (position, abi::ListenResponse::from_row(row))
+        })
+        .collect();
+    let pending = changes.len() < rows.len();
+    Ok((changes, pending))
 }
 
 // fn _string_to_option(s: &str) -> Option<String> {
@@ -213,21 +1154,21 @@ impl Rsvp for ReservationManager {
 // }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use abi::{
         Reservation, ReservationConflict, ReservationConflictInfo, ReservationFilterBuilder,
         ReservationQueryBuilder, ResrvationWindow,
     };
     // use sqlx::types::uuid::Timestamp;
-    use prost_types::Timestamp;
     use sqlx_db_test::TestDb;
 
     use super::*;
+    use crate::Catalog;
 
     #[tokio::test]
     async fn reserve_should_work_for_valid_window() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, _manager) = make_tyr_reservation(migrated_pool.clone()).await;
         assert!(rsvp.id != 0);
     }
@@ -235,8 +1176,8 @@ mod tests {
     #[tokio::test]
     async fn reserve_confilct_reservation_should_reject() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
-        let (_rsvp1, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp1, manager) = make_tyr_reservation(migrated_pool.clone()).await;
         let rsvp2 = abi::Reservation::new_pending(
             "aliceid",
             "ocean-view-room-713",
@@ -248,7 +1189,7 @@ mod tests {
         let err = manager.reserve(rsvp2).await.unwrap_err();
         // println!("{:?}", err);
 
-        let info = ReservationConflictInfo::Parsed(ReservationConflict {
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
             new: ResrvationWindow {
                 rid: "ocean-view-room-713".to_string(),
                 start: "2023-12-26T15:00:00-0700".parse().unwrap(),
@@ -259,47 +1200,1539 @@ mod tests {
                 start: "2023-12-25T15:00:00-0700".parse().unwrap(),
                 end: "2023-12-28T12:00:00-0700".parse().unwrap(),
             },
-        });
-
+            // tyr's reservation is not alice's business
+            existing: vec![conflicting(&rsvp1, "", abi::ReservationStatus::Pending)],
+            saturated: vec![ResrvationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2023-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-28T12:00:00-0700".parse().unwrap(),
+            }],
+        }));
+
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+
+        // if let abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(info)) = err {
+        //     assert_eq!(info.old.rid, "ocean-view-room-713");
+        //     assert_eq!(info.old.start.to_rfc3339(), "2023-12-25T22:00:00+00:00");
+        //     assert_eq!(info.old.end.to_rfc3339(), "2023-12-28T19:00:00+00:00");
+        // } else {
+        //     panic!("expect conflict reservation error");
+        // }
+    }
+
+    #[tokio::test]
+    async fn reserve_conflict_should_list_every_overlapping_reservation() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (tyr, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let (alice, _) = make_reservation(
+            migrated_pool.clone(),
+            "aliceid",
+            "ocean-view-room-713",
+            "2023-12-28T13:00:00-0700",
+            "2023-12-31T12:00:00-0700",
+            "hello.",
+        )
+        .await;
+        let alice = manager.change_status(alice.id).await.unwrap();
+        // cancelled reservations don't hold the window anymore
+        let (cancelled, _) = make_reservation(
+            migrated_pool.clone(),
+            "bobid",
+            "ocean-view-room-713",
+            "2024-01-01T12:00:00-0700",
+            "2024-01-02T12:00:00-0700",
+            "hello.",
+        )
+        .await;
+        manager.delete(cancelled.id).await.unwrap();
+
+        let rsvp = abi::Reservation::new_pending(
+            "aliceid",
+            "ocean-view-room-713",
+            "2023-12-27T15:00:00-0700".parse().unwrap(),
+            "2024-01-01T15:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+        let err = manager.reserve(rsvp).await.unwrap_err();
+        match err {
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(info)) => {
+                assert_eq!(
+                    info.existing,
+                    vec![
+                        conflicting(&tyr, "", abi::ReservationStatus::Pending),
+                        conflicting(&alice, "aliceid", abi::ReservationStatus::Confirmed),
+                    ]
+                );
+            }
+            _ => panic!("expect conflict reservation error, got {:?}", err),
+        }
+    }
+
+    #[tokio::test]
+    async fn reserve_on_pool_should_allow_up_to_capacity() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let desks = abi::Resource::new_pool("desks", "Hot desks", "desk", 2);
+        manager.create_resource(desks.clone()).await.unwrap();
+        let desk = |uid: &str, start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                uid,
+                "desks",
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "hot desk",
+            )
+        };
+
+        let alice = manager
+            .reserve(desk(
+                "aliceid",
+                "2024-03-01T10:00:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+        let bob = manager
+            .reserve(desk(
+                "bobid",
+                "2024-03-01T11:00:00Z",
+                "2024-03-01T13:00:00Z",
+            ))
+            .await
+            .unwrap();
+
+        // both desks are taken from 11:30 to 12:00
+        let err = manager
+            .reserve(desk(
+                "tyrid",
+                "2024-03-01T11:30:00Z",
+                "2024-03-01T14:00:00Z",
+            ))
+            .await
+            .unwrap_err();
+        let window = |start: &str, end: &str| ResrvationWindow {
+            rid: "desks".to_string(),
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        };
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: window("2024-03-01T11:30:00Z", "2024-03-01T14:00:00Z"),
+            old: window("2024-03-01T11:30:00Z", "2024-03-01T12:00:00Z"),
+            existing: vec![
+                conflicting(&alice, "", abi::ReservationStatus::Pending),
+                conflicting(&bob, "", abi::ReservationStatus::Pending),
+            ],
+            saturated: vec![window("2024-03-01T11:30:00Z", "2024-03-01T12:00:00Z")],
+        }));
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+
+        let tyr = manager
+            .reserve(desk(
+                "tyrid",
+                "2024-03-01T12:30:00Z",
+                "2024-03-01T14:00:00Z",
+            ))
+            .await
+            .unwrap();
+
+        // free slots are where a desk is left
+        let query = abi::AvailabilityRequest {
+            resource_ids: vec!["desks".into()],
+            start: Some("2024-03-01T10:00:00Z".parse().unwrap()),
+            end: Some("2024-03-01T14:00:00Z".parse().unwrap()),
+            min_duration: 0,
+        };
+        let slots: Vec<_> = manager
+            .find_free_slots(query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|slot| (slot.resource_id, slot.start.unwrap(), slot.end.unwrap()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                free_slot("desks", "2024-03-01T10:00:00Z", "2024-03-01T11:00:00Z"),
+                free_slot("desks", "2024-03-01T12:00:00Z", "2024-03-01T12:30:00Z"),
+                free_slot("desks", "2024-03-01T13:00:00Z", "2024-03-01T14:00:00Z"),
+            ]
+        );
+
+        // the overlapping reservations don't fit in a single desk anymore
+        let err = manager
+            .update_resource(abi::Resource::new_pool("desks", "Hot desks", "desk", 1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        // a cancelled reservation gives its desk back
+        manager.delete(bob.id).await.unwrap();
+        manager
+            .reserve(desk(
+                "tyrid",
+                "2024-03-01T11:30:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(manager.get(tyr.id).await.unwrap(), tyr);
+    }
+
+    #[tokio::test]
+    async fn blackout_on_pool_should_take_every_unit() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let desks = abi::Resource::new_pool("desks", "Hot desks", "desk", 3);
+        manager.create_resource(desks).await.unwrap();
+        let time = |v: &str| v.parse().unwrap();
+        manager
+            .reserve(abi::Reservation::new_pending(
+                "aliceid",
+                "desks",
+                time("2024-03-01T10:00:00Z"),
+                time("2024-03-01T12:00:00Z"),
+                "hot desk",
+            ))
+            .await
+            .unwrap();
+
+        let blackout = abi::Reservation::new_blackout(
+            "desks",
+            time("2024-03-01T13:00:00Z"),
+            time("2024-03-01T15:00:00Z"),
+            "new carpet",
+        );
+        manager
+            .blackout(blackout, abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap();
+
+        // two desks are left next to alice, but none during the blackout
+        let err = manager
+            .reserve(abi::Reservation::new_pending(
+                "bobid",
+                "desks",
+                time("2024-03-01T14:00:00Z"),
+                time("2024-03-01T16:00:00Z"),
+                "hot desk",
+            ))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        // and a blackout can't share the pool with alice either
+        let blackout = abi::Reservation::new_blackout(
+            "desks",
+            time("2024-03-01T11:00:00Z"),
+            time("2024-03-01T12:00:00Z"),
+            "new carpet",
+        );
+        let err = manager
+            .blackout(blackout, abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        let at = |v: &str| convert_to_timestamp(&v.parse().unwrap());
+        let slots = manager
+            .find_free_slots(abi::AvailabilityRequest {
+                resource_ids: vec!["desks".into()],
+                start: Some(at("2024-03-01T09:00:00Z")),
+                end: Some(at("2024-03-01T17:00:00Z")),
+                min_duration: 0,
+            })
+            .await
+            .unwrap();
+        let windows: Vec<_> = slots
+            .iter()
+            .map(|slot| (slot.start.clone().unwrap(), slot.end.clone().unwrap()))
+            .collect();
+        assert_eq!(
+            windows,
+            vec![
+                (at("2024-03-01T09:00:00Z"), at("2024-03-01T13:00:00Z")),
+                (at("2024-03-01T15:00:00Z"), at("2024-03-01T17:00:00Z")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_reserve_on_pool_should_not_exceed_capacity() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let seats = abi::Resource::new_pool("seats", "License seats", "license", 2);
+        manager.create_resource(seats).await.unwrap();
+
+        let tasks: Vec<_> = (0..6)
+            .map(|i| {
+                let manager = manager.clone();
+                let rsvp = abi::Reservation::new_pending(
+                    format!("user-{}", i),
+                    "seats",
+                    "2024-03-01T10:00:00Z".parse().unwrap(),
+                    "2024-03-01T12:00:00Z".parse().unwrap(),
+                    "license seat",
+                );
+                tokio::spawn(async move { manager.reserve(rsvp).await })
+            })
+            .collect();
+
+        let mut reserved = 0;
+        for task in tasks {
+            match task.await.unwrap() {
+                Ok(_) => reserved += 1,
+                Err(e) => assert!(matches!(e, abi::Error::ConflictReservation(_))),
+            }
+        }
+        assert_eq!(reserved, 2);
+    }
+
+    #[tokio::test]
+    async fn reserve_many_should_make_all_reservations() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvps = batch_reservations(&["room-1", "projector-1", "rig-1"]);
+
+        let reserved = manager.reserve_many(rsvps.clone()).await.unwrap();
+        assert_eq!(reserved.len(), 3);
+        for (rsvp, mut expected) in reserved.into_iter().zip(rsvps) {
+            assert!(rsvp.id != 0);
+            expected.id = rsvp.id;
+            expected.blocked_start = expected.start.clone();
+            expected.blocked_end = expected.end.clone();
+            assert_eq!(rsvp, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn reserve_many_conflict_should_make_nothing() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (tyr, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let rsvps = batch_reservations(&["room-1", "ocean-view-room-713", "rig-1"]);
+
+        let err = manager.reserve_many(rsvps).await.unwrap_err();
+        match err {
+            abi::Error::BatchConflict(1, ReservationConflictInfo::Parsed(info)) => {
+                assert_eq!(info.new.rid, "ocean-view-room-713");
+                assert_eq!(
+                    info.existing,
+                    vec![conflicting(&tyr, "", abi::ReservationStatus::Pending)]
+                );
+            }
+            _ => panic!("expect conflict reservation error, got {:?}", err),
+        }
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM rsvp.reservations")
+            .fetch_one(&migrated_pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn reserve_many_empty_batch_should_reject() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+
+        let err = manager.reserve_many(vec![]).await.unwrap_err();
+        assert_eq!(err, abi::Error::EmptyBatch);
+    }
+
+    #[tokio::test]
+    async fn reserve_with_status_other_than_pending_should_reject() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+
+        let mut rsvps = batch_reservations(&["room-1", "room-2"]);
+        rsvps[1].status = abi::ReservationStatus::Confirmed as i32;
+        let err = manager.reserve(rsvps[1].clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(abi::ReservationStatus::Confirmed as i32)
+        );
+        let err = manager.reserve_many(rsvps.clone()).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(abi::ReservationStatus::Confirmed as i32)
+        );
+        let err = manager
+            .reserve_recurring(rsvps[1].clone(), weekly_rule(3))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(abi::ReservationStatus::Confirmed as i32)
+        );
+
+        // an unset status is made pending
+        rsvps[0].status = abi::ReservationStatus::Unknown as i32;
+        let rsvp = manager.reserve(rsvps.remove(0)).await.unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Pending as i32);
+        let rsvp = manager.get(rsvp.id).await.unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Pending as i32);
+    }
+
+    #[tokio::test]
+    async fn reserve_recurring_should_skip_conflicting_occurrences() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (_rsvp, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+
+        let resp = manager
+            .reserve_recurring(weekly_reservation(), weekly_rule(3))
+            .await
+            .unwrap();
+
+        assert!(resp.series_id > 0);
+        assert_eq!(resp.reservations.len(), 2);
+        assert!(resp
+            .reservations
+            .iter()
+            .all(|rsvp| rsvp.id > 0 && rsvp.series_id == resp.series_id));
+        assert_eq!(resp.conflicts.len(), 1);
+        assert_eq!(
+            resp.conflicts[0].start,
+            Some(convert_to_timestamp(
+                &"2023-12-25T16:00:00-0700".parse().unwrap()
+            ))
+        );
+
+        let rsvp = manager.get(resp.reservations[1].id).await.unwrap();
+        assert_eq!(rsvp.series_id, resp.series_id);
+    }
+
+    #[tokio::test]
+    async fn reserve_recurring_all_conflicting_should_make_nothing() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (_rsvp, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let rsvp = abi::Reservation::new_pending(
+            "aliceid",
+            "ocean-view-room-713",
+            "2023-12-25T16:00:00-0700".parse().unwrap(),
+            "2023-12-25T17:00:00-0700".parse().unwrap(),
+            "weekly sync",
+        );
+
+        let err = manager
+            .reserve_recurring(rsvp, weekly_rule(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM rsvp.reservations WHERE series_id IS NOT NULL",
+        )
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn update_and_cancel_series_should_touch_every_occurrence() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let resp = manager
+            .reserve_recurring(weekly_reservation(), weekly_rule(3))
+            .await
+            .unwrap();
+        let single = manager
+            .reserve(batch_reservations(&["room-1"]).remove(0))
+            .await
+            .unwrap();
+
+        let updated = manager
+            .update_series(resp.series_id, Some("moved to room 2".into()), 0)
+            .await
+            .unwrap();
+        assert_eq!(updated.len(), 3);
+        assert!(updated.iter().all(|rsvp| rsvp.note == "moved to room 2"));
+
+        // a single occurrence could still be cancelled on its own
+        manager.delete(resp.reservations[0].id).await.unwrap();
+        let cancelled = manager.cancel_series(resp.series_id).await.unwrap();
+        assert_eq!(cancelled.len(), 2);
+        assert_eq!(cancelled[0].id, resp.reservations[1].id);
+
+        let err = manager.cancel_series(resp.series_id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        assert_eq!(manager.get(single.id).await.unwrap().series_id, 0);
+    }
+
+    #[tokio::test]
+    async fn update_series_should_move_every_occurrence_or_none() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let resp = manager
+            .reserve_recurring(weekly_reservation(), weekly_rule(3))
+            .await
+            .unwrap();
+        let week = 7 * 24 * 3600;
+
+        // each occurrence lands on the window of the next one
+        let moved = manager
+            .update_series(resp.series_id, None, week)
+            .await
+            .unwrap();
+        assert_eq!(moved.len(), 3);
+        for (rsvp, old) in moved.iter().zip(&resp.reservations) {
+            assert_eq!(rsvp.id, old.id);
+            assert_eq!(rsvp.note, "weekly sync");
+            let start = convert_to_utc_time(old.start.as_ref().unwrap());
+            assert_eq!(
+                rsvp.start,
+                Some(convert_to_timestamp(&(start + chrono::Duration::weeks(1))))
+            );
+        }
+
+        manager
+            .reserve(abi::Reservation::new_pending(
+                "tyrid",
+                "ocean-view-room-713",
+                "2024-01-15T16:00:00-0700".parse().unwrap(),
+                "2024-01-15T17:00:00-0700".parse().unwrap(),
+                "hello.",
+            ))
+            .await
+            .unwrap();
+        let err = manager
+            .update_series(resp.series_id, Some("late".into()), 2 * week)
+            .await
+            .unwrap_err();
+        match err {
+            abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(info)) => {
+                assert_eq!(info.new.rid, "ocean-view-room-713");
+            }
+            _ => panic!("expect conflict reservation error, got {:?}", err),
+        }
+        for rsvp in &moved {
+            assert_eq!(&manager.get(rsvp.id).await.unwrap(), rsvp);
+        }
+    }
+
+    #[tokio::test]
+    async fn reap_expired_should_cancel_expired_holds_only() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let past = convert_to_timestamp(&"2023-01-01T00:00:00Z".parse().unwrap());
+        let mut rsvps = batch_reservations(&["room-1", "room-2", "room-3", "room-4"]);
+        for rsvp in &mut rsvps[..3] {
+            rsvp.hold_for(3600).unwrap();
+        }
+        let rsvps = manager.reserve_many(rsvps).await.unwrap();
+        // a past expiry is rejected on reserve, let the first two holds run out
+        sqlx::query("UPDATE rsvp.reservations SET hold_expires_at = $1 WHERE id = ANY($2)")
+            .bind(convert_to_utc_time(&past))
+            .bind(vec![rsvps[0].id, rsvps[1].id])
+            .execute(&migrated_pool)
+            .await
+            .unwrap();
+
+        // confirmed reservations are no longer on hold
+        let confirmed = manager.change_status(rsvps[1].id).await.unwrap();
+        assert!(confirmed.hold_expires_at.is_none());
+
+        let reaped = manager.reap_expired().await.unwrap();
+        assert_eq!(reaped.len(), 1);
+        assert_eq!(reaped[0].id, rsvps[0].id);
+        assert_eq!(reaped[0].hold_expires_at, Some(past));
+        assert_eq!(reaped[0].status, abi::ReservationStatus::Cancelled as i32);
+        assert_eq!(manager.get(rsvps[0].id).await.unwrap(), reaped[0]);
+
+        let op: String = sqlx::query_scalar(
+            "SELECT op::text FROM rsvp.reservation_changes WHERE reservation_id = $1 ORDER BY id DESC LIMIT 1",
+        )
+        .bind(rsvps[0].id)
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+        assert_eq!(op, "delete");
+
+        for rsvp in &rsvps[1..] {
+            assert!(manager.get(rsvp.id).await.is_ok());
+        }
+        assert!(manager.reap_expired().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_should_confirm_pending() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let rsvp = manager.change_status(rsvp.id).await.unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_twice_should_return_confirmed() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let confirmed = manager.change_status(rsvp.id).await.unwrap();
+        // confirm again returns the reservation unchanged
+        let ret = manager.change_status(rsvp.id).await.unwrap();
+        assert_eq!(ret, confirmed);
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_blocked_should_reject() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = batch_reservations(&["room-1"]).remove(0);
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+        let rsvp = manager
+            .transition(rsvp.id, abi::ReservationStatus::Blocked)
+            .await
+            .unwrap();
+
+        let err = manager.change_status(rsvp.id).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatusTransition(
+                abi::ReservationStatus::Blocked,
+                abi::ReservationStatus::Confirmed
+            )
+        );
+        let rsvp = manager.get(rsvp.id).await.unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Blocked as i32);
+    }
+
+    #[tokio::test]
+    async fn transition_should_follow_status_state_machine() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+
+        // block for maintenance, then release it back to its owner
+        let rsvp = manager
+            .transition(rsvp.id, abi::ReservationStatus::Blocked)
+            .await
+            .unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Blocked as i32);
+        let rsvp = manager
+            .transition(rsvp.id, abi::ReservationStatus::Pending)
+            .await
+            .unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Pending as i32);
+
+        let rsvp = manager
+            .transition(rsvp.id, abi::ReservationStatus::Confirmed)
+            .await
+            .unwrap();
+        assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+
+        let err = manager
+            .transition(rsvp.id, abi::ReservationStatus::Pending)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatusTransition(
+                abi::ReservationStatus::Confirmed,
+                abi::ReservationStatus::Pending
+            )
+        );
+        let err = manager
+            .transition(rsvp.id, abi::ReservationStatus::Unknown)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatusTransition(
+                abi::ReservationStatus::Confirmed,
+                abi::ReservationStatus::Unknown
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn blackout_should_apply_policy_to_overlapping_pending() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvps = manager
+            .reserve_many(batch_reservations(&["room-1", "room-2", "room-3"]))
+            .await
+            .unwrap();
+        manager.change_status(rsvps[2].id).await.unwrap();
+
+        let err = manager
+            .blackout(lab_blackout("room-1"), abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+
+        // flagged reservations give up their window until they are moved
+        let resp = manager
+            .blackout(lab_blackout("room-1"), abi::BlackoutPolicy::FlagPending)
+            .await
+            .unwrap();
+        assert!(resp.blackout.unwrap().is_blackout());
+        assert_eq!(resp.affected.len(), 1);
+        assert_eq!(resp.affected[0].id, rsvps[0].id);
+        assert!(resp.affected[0].flagged);
+        let err = manager.change_status(rsvps[0].id).await.unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        let rsvp = manager
+            .reschedule(
+                rsvps[0].id,
+                "2024-01-26T15:00:00-0700".parse().unwrap(),
+                "2024-01-30T12:00:00-0700".parse().unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(!rsvp.flagged);
+
+        let resp = manager
+            .blackout(lab_blackout("room-2"), abi::BlackoutPolicy::CancelPending)
+            .await
+            .unwrap();
+        assert_eq!(resp.affected.len(), 1);
+        assert_eq!(
+            resp.affected[0].status,
+            abi::ReservationStatus::Cancelled as i32
+        );
+        assert_eq!(manager.get(rsvps[1].id).await.unwrap(), resp.affected[0]);
+
+        // confirmed reservations are never touched
+        let err = manager
+            .blackout(lab_blackout("room-3"), abi::BlackoutPolicy::CancelPending)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+    }
+
+    #[tokio::test]
+    async fn query_should_include_blackouts_only_if_asked() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = manager
+            .reserve(batch_reservations(&["room-1"]).remove(0))
+            .await
+            .unwrap();
+        let mut blackout = lab_blackout("room-1");
+        blackout.start = Some("2024-01-01T00:00:00-0700".parse().unwrap());
+        blackout.end = Some("2024-01-02T00:00:00-0700".parse().unwrap());
+        let blackout = manager
+            .blackout(blackout, abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap()
+            .blackout
+            .unwrap();
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("aliceid")
+            .resource_id("room-1")
+            .build()
+            .unwrap();
+        let rsvps = query_all(&manager, query.clone()).await;
+        assert_eq!(rsvps, vec![rsvp.clone()]);
+
+        let query = abi::ReservationQuery {
+            include_blackouts: true,
+            ..query
+        };
+        let rsvps = query_all(&manager, query).await;
+        assert_eq!(rsvps, vec![rsvp, blackout.clone()]);
+
+        let filter = ReservationFilterBuilder::default()
+            .status(abi::ReservationStatus::Blocked as i32)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert!(rsvps.is_empty());
+
+        let err = manager
+            .transition(blackout.id, abi::ReservationStatus::Pending)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatusTransition(
+                abi::ReservationStatus::Blocked,
+                abi::ReservationStatus::Pending
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn find_free_slots_should_return_gaps_between_reservations() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (_rsvp, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let mut query = abi::AvailabilityRequest {
+            resource_ids: vec!["ocean-view-room-713".into(), "ocean-view-room-714".into()],
+            start: Some("2023-12-25T00:00:00-0700".parse().unwrap()),
+            end: Some("2023-12-29T00:00:00-0700".parse().unwrap()),
+            min_duration: 0,
+        };
+
+        let slots = manager.find_free_slots(query.clone()).await.unwrap();
+        let slots: Vec<_> = slots
+            .into_iter()
+            .map(|slot| (slot.resource_id, slot.start.unwrap(), slot.end.unwrap()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                free_slot(
+                    "ocean-view-room-713",
+                    "2023-12-25T00:00:00-0700",
+                    "2023-12-25T15:00:00-0700"
+                ),
+                free_slot(
+                    "ocean-view-room-713",
+                    "2023-12-28T12:00:00-0700",
+                    "2023-12-29T00:00:00-0700"
+                ),
+                free_slot(
+                    "ocean-view-room-714",
+                    "2023-12-25T00:00:00-0700",
+                    "2023-12-29T00:00:00-0700"
+                ),
+            ]
+        );
+
+        // the 12 hours slot after the reservation is too short
+        query.min_duration = 13 * 3600;
+        let slots = manager.find_free_slots(query).await.unwrap();
+        assert_eq!(slots.len(), 2);
+        assert_eq!(
+            slots[0].end,
+            Some("2023-12-25T15:00:00-0700".parse().unwrap())
+        );
+        assert_eq!(slots[1].resource_id, "ocean-view-room-714");
+    }
+
+    #[tokio::test]
+    async fn find_free_slots_should_skip_unknown_and_inactive_resources() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .deactivate_resource("ocean-view-room-714".into())
+            .await
+            .unwrap();
+        let query = abi::AvailabilityRequest {
+            resource_ids: vec![
+                "ocean-view-room-713".into(),
+                "ocean-view-room-714".into(),
+                "no-such-room".into(),
+            ],
+            start: Some("2023-12-25T00:00:00-0700".parse().unwrap()),
+            end: Some("2023-12-29T00:00:00-0700".parse().unwrap()),
+            min_duration: 0,
+        };
+
+        let slots = manager.find_free_slots(query).await.unwrap();
+        let slots: Vec<_> = slots
+            .into_iter()
+            .map(|slot| (slot.resource_id, slot.start.unwrap(), slot.end.unwrap()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![free_slot(
+                "ocean-view-room-713",
+                "2023-12-25T00:00:00-0700",
+                "2023-12-29T00:00:00-0700"
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn suggest_windows_should_return_nearest_free_windows() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        // a one hour window in the middle of the existing reservation
+        let conflicting = abi::Reservation::new_pending(
+            "aliceid",
+            "ocean-view-room-713",
+            "2023-12-26T15:00:00-0700".parse().unwrap(),
+            "2023-12-26T16:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+
+        let slots = manager
+            .suggest_windows(conflicting.clone(), vec![], 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            slots,
+            vec![
+                abi::FreeSlot {
+                    resource_id: "ocean-view-room-713".into(),
+                    start: Some("2023-12-25T14:00:00-0700".parse().unwrap()),
+                    end: rsvp.start.clone(),
+                },
+                abi::FreeSlot {
+                    resource_id: "ocean-view-room-713".into(),
+                    start: rsvp.end.clone(),
+                    end: Some("2023-12-28T13:00:00-0700".parse().unwrap()),
+                },
+            ]
+        );
+
+        let slots = manager
+            .suggest_windows(conflicting.clone(), vec!["ocean-view-room-714".into()], 1)
+            .await
+            .unwrap();
+        assert_eq!(slots[0].resource_id, "ocean-view-room-714");
+        assert_eq!(slots[0].start, conflicting.start);
+    }
+
+    #[tokio::test]
+    async fn buffers_should_pad_the_blocked_window() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        // 15 minutes to set the lab up, 30 minutes to reset it
+        let lab = abi::Resource {
+            buffer_before: 15 * 60,
+            buffer_after: 30 * 60,
+            ..abi::Resource::new("lab-1", "Lab 1", "lab")
+        };
+        manager.create_resource(lab).await.unwrap();
+        let rsvp = |uid: &str, start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                uid,
+                "lab-1",
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "lab session",
+            )
+        };
+        let ts = |s: &str| -> Option<Timestamp> { Some(s.parse().unwrap()) };
+
+        // start and end stay the requested window
+        let alice = manager
+            .reserve(rsvp(
+                "aliceid",
+                "2024-03-01T10:00:00Z",
+                "2024-03-01T11:00:00Z",
+            ))
+            .await
+            .unwrap();
+        assert_eq!(alice.start, ts("2024-03-01T10:00:00Z"));
+        assert_eq!(alice.blocked_start, ts("2024-03-01T09:45:00Z"));
+        assert_eq!(alice.blocked_end, ts("2024-03-01T11:30:00Z"));
+        assert_eq!(manager.get(alice.id).await.unwrap(), alice);
+
+        // the next reservation needs the reset after alice and its own set up
+        let err = manager
+            .reserve(rsvp(
+                "bobid",
+                "2024-03-01T11:30:00Z",
+                "2024-03-01T12:30:00Z",
+            ))
+            .await
+            .unwrap_err();
+        let window = |start: &str, end: &str| ResrvationWindow {
+            rid: "lab-1".to_string(),
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        };
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: window("2024-03-01T11:15:00Z", "2024-03-01T13:00:00Z"),
+            old: window("2024-03-01T09:45:00Z", "2024-03-01T11:30:00Z"),
+            existing: vec![conflicting(&alice, "", abi::ReservationStatus::Pending)],
+            saturated: vec![window("2024-03-01T11:15:00Z", "2024-03-01T11:30:00Z")],
+        }));
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+        manager
+            .reserve(rsvp(
+                "bobid",
+                "2024-03-01T11:45:00Z",
+                "2024-03-01T12:45:00Z",
+            ))
+            .await
+            .unwrap();
+
+        // free slots leave room for the buffers of a new reservation
+        let query = abi::AvailabilityRequest {
+            resource_ids: vec!["lab-1".into()],
+            start: ts("2024-03-01T08:00:00Z"),
+            end: ts("2024-03-01T16:00:00Z"),
+            min_duration: 0,
+        };
+        let slots: Vec<_> = manager
+            .find_free_slots(query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|slot| (slot.resource_id, slot.start.unwrap(), slot.end.unwrap()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                free_slot("lab-1", "2024-03-01T08:00:00Z", "2024-03-01T09:15:00Z"),
+                free_slot("lab-1", "2024-03-01T13:30:00Z", "2024-03-01T16:00:00Z"),
+            ]
+        );
+
+        // blackouts are not padded, but can't overlap the buffers of a reservation
+        let blackout = abi::Reservation::new_blackout(
+            "lab-1",
+            "2024-03-01T13:00:00Z".parse().unwrap(),
+            "2024-03-01T14:00:00Z".parse().unwrap(),
+            "maintenance",
+        );
+        let err = manager
+            .blackout(blackout.clone(), abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, abi::Error::ConflictReservation(_)));
+        let blackout = abi::Reservation {
+            start: ts("2024-03-01T13:15:00Z"),
+            ..blackout
+        };
+        let blackout = manager
+            .blackout(blackout, abi::BlackoutPolicy::Reject)
+            .await
+            .unwrap()
+            .blackout
+            .unwrap();
+        assert_eq!(blackout.blocked_start, blackout.start);
+    }
+
+    #[tokio::test]
+    async fn reserve_should_conflict_across_resource_hierarchy() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        for resource in [
+            abi::Resource::new("lab-1", "Lab 1", "lab"),
+            abi::Resource::new_child("bench-1", "Bench 1", "bench", "lab-1"),
+            abi::Resource::new_child("bench-2", "Bench 2", "bench", "lab-1"),
+        ] {
+            manager.create_resource(resource).await.unwrap();
+        }
+        let rsvp = |rid: &str, start: &str, end: &str| {
+            abi::Reservation::new_pending(
+                "aliceid",
+                rid,
+                start.parse().unwrap(),
+                end.parse().unwrap(),
+                "experiment",
+            )
+        };
+        let window = |rid: &str, start: &str, end: &str| ResrvationWindow {
+            rid: rid.to_string(),
+            start: start.parse().unwrap(),
+            end: end.parse().unwrap(),
+        };
+
+        // sibling benches don't block each other
+        let bench1 = manager
+            .reserve(rsvp(
+                "bench-1",
+                "2024-03-01T10:00:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+        let bench2 = manager
+            .reserve(rsvp(
+                "bench-2",
+                "2024-03-01T11:00:00Z",
+                "2024-03-01T12:00:00Z",
+            ))
+            .await
+            .unwrap();
+
+        // the whole lab can't be booked while a bench is
+        let err = manager
+            .reserve(rsvp(
+                "lab-1",
+                "2024-03-01T11:00:00Z",
+                "2024-03-01T13:00:00Z",
+            ))
+            .await
+            .unwrap_err();
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: window("lab-1", "2024-03-01T11:00:00Z", "2024-03-01T13:00:00Z"),
+            old: window("bench-1", "2024-03-01T10:00:00Z", "2024-03-01T12:00:00Z"),
+            existing: vec![
+                conflicting(&bench1, "aliceid", abi::ReservationStatus::Pending),
+                conflicting(&bench2, "aliceid", abi::ReservationStatus::Pending),
+            ],
+            saturated: vec![window(
+                "lab-1",
+                "2024-03-01T11:00:00Z",
+                "2024-03-01T12:00:00Z",
+            )],
+        }));
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+
+        // and a bench can't be booked while the whole lab is
+        let lab = manager
+            .reserve(rsvp(
+                "lab-1",
+                "2024-03-01T13:00:00Z",
+                "2024-03-01T15:00:00Z",
+            ))
+            .await
+            .unwrap();
+        let err = manager
+            .reserve(rsvp(
+                "bench-2",
+                "2024-03-01T14:00:00Z",
+                "2024-03-01T16:00:00Z",
+            ))
+            .await
+            .unwrap_err();
+        let abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(conflict)) = err else {
+            panic!("expect conflict reservation error, got {:?}", err);
+        };
+        assert_eq!(conflict.old.rid, "lab-1");
+        assert_eq!(
+            conflict.existing,
+            vec![conflicting(
+                &lab,
+                "aliceid",
+                abi::ReservationStatus::Pending
+            )]
+        );
+
+        let query = abi::AvailabilityRequest {
+            resource_ids: vec!["bench-1".into()],
+            start: Some("2024-03-01T09:00:00Z".parse().unwrap()),
+            end: Some("2024-03-01T16:00:00Z".parse().unwrap()),
+            min_duration: 0,
+        };
+        let slots: Vec<_> = manager
+            .find_free_slots(query)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|slot| (slot.resource_id, slot.start.unwrap(), slot.end.unwrap()))
+            .collect();
+        assert_eq!(
+            slots,
+            vec![
+                free_slot("bench-1", "2024-03-01T09:00:00Z", "2024-03-01T10:00:00Z"),
+                free_slot("bench-1", "2024-03-01T12:00:00Z", "2024-03-01T13:00:00Z"),
+                free_slot("bench-1", "2024-03-01T15:00:00Z", "2024-03-01T16:00:00Z"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_reserve_on_hierarchy_should_not_overlap() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .create_resource(abi::Resource::new("lab-1", "Lab 1", "lab"))
+            .await
+            .unwrap();
+        let mut rids = vec!["lab-1".to_string()];
+        for i in 0..5 {
+            let bench = abi::Resource::new_child(format!("bench-{}", i), "", "bench", "lab-1");
+            rids.push(manager.create_resource(bench).await.unwrap().id);
+        }
+
+        // either the lab or every bench
+        let tasks: Vec<_> = rids
+            .into_iter()
+            .map(|rid| {
+                let manager = manager.clone();
+                let rsvp = abi::Reservation::new_pending(
+                    "aliceid",
+                    rid,
+                    "2024-03-01T10:00:00Z".parse().unwrap(),
+                    "2024-03-01T12:00:00Z".parse().unwrap(),
+                    "experiment",
+                );
+                tokio::spawn(async move { manager.reserve(rsvp).await })
+            })
+            .collect();
+
+        let mut reserved = vec![];
+        for task in tasks {
+            match task.await.unwrap() {
+                Ok(rsvp) => reserved.push(rsvp.resource_id),
+                Err(e) => assert!(matches!(e, abi::Error::ConflictReservation(_))),
+            }
+        }
+        assert!(reserved == ["lab-1"] || !reserved.contains(&"lab-1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn reserve_any_should_pick_member_by_strategy() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .create_resource_group(abi::ResourceGroup::new(
+                "rooms",
+                "Rooms",
+                ["room-1", "room-2", "room-3"],
+            ))
+            .await
+            .unwrap();
+        // least used only counts upcoming reservations
+        let rsvp = |day: u32| {
+            abi::Reservation::new_pending(
+                "aliceid",
+                "",
+                format!("2099-01-{:02}T10:00:00Z", day).parse().unwrap(),
+                format!("2099-01-{:02}T12:00:00Z", day).parse().unwrap(),
+                "standup",
+            )
+        };
+        let reserve_any = |day: u32, strategy| {
+            let manager = manager.clone();
+            async move {
+                manager
+                    .reserve_any(rsvp(day), "rooms".into(), strategy)
+                    .await
+                    .map(|rsvp| rsvp.resource_id)
+            }
+        };
+        use abi::SelectionStrategy::*;
+
+        // first fit takes the members in order until the group is full
+        for rid in ["room-1", "room-2", "room-3"] {
+            assert_eq!(reserve_any(1, FirstFit).await.unwrap(), rid);
+        }
+        let err = reserve_any(1, FirstFit).await.unwrap_err();
+        assert_eq!(err, abi::Error::NoAvailableResource("rooms".into()));
+
+        // room-1 is the busiest, then room-2
+        manager
+            .reserve(abi::Reservation {
+                resource_id: "room-1".into(),
+                ..rsvp(2)
+            })
+            .await
+            .unwrap();
+        assert_eq!(reserve_any(3, LeastUsed).await.unwrap(), "room-2");
+        assert_eq!(reserve_any(3, LeastUsed).await.unwrap(), "room-3");
+
+        // round robin starts after the last picked member and wraps around
+        assert_eq!(reserve_any(4, RoundRobin).await.unwrap(), "room-1");
+        assert_eq!(reserve_any(4, RoundRobin).await.unwrap(), "room-2");
+        assert_eq!(reserve_any(5, RoundRobin).await.unwrap(), "room-3");
+        assert_eq!(reserve_any(5, RoundRobin).await.unwrap(), "room-1");
+
+        let err = manager
+            .reserve_any(rsvp(6), "desks".into(), FirstFit)
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn reserve_any_should_work_with_a_single_connection() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .create_resource_group(abi::ResourceGroup::new("rooms", "Rooms", ["room-1"]))
+            .await
+            .unwrap();
+        let pool = PgPoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(3))
+            .connect(&tdb.url())
+            .await
+            .unwrap();
+        let manager = ReservationManager::new(pool);
+
+        let rsvp = abi::Reservation::new_pending(
+            "aliceid",
+            "",
+            "2099-01-01T10:00:00Z".parse().unwrap(),
+            "2099-01-01T12:00:00Z".parse().unwrap(),
+            "standup",
+        );
+        let reserved = manager
+            .reserve_any(rsvp, "rooms".into(), abi::SelectionStrategy::FirstFit)
+            .await
+            .unwrap();
+        assert_eq!(reserved.resource_id, "room-1");
+    }
+
+    #[tokio::test]
+    async fn reserve_any_should_validate_the_reservation() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        manager
+            .create_resource_group(abi::ResourceGroup::new("rooms", "Rooms", ["room-1"]))
+            .await
+            .unwrap();
+        let rsvp = abi::Reservation::new_pending(
+            "aliceid",
+            "",
+            "2099-01-01T10:00:00Z".parse().unwrap(),
+            "2099-01-01T12:00:00Z".parse().unwrap(),
+            "standup",
+        );
+        let reserve_any =
+            |rsvp| manager.reserve_any(rsvp, "rooms".into(), abi::SelectionStrategy::FirstFit);
+
+        let past = convert_to_timestamp(&(Utc::now() - chrono::Duration::seconds(1)));
+        let expired = abi::Reservation {
+            hold_expires_at: Some(past.clone()),
+            ..rsvp.clone()
+        };
+        let err = reserve_any(expired).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidHoldExpiry(past.seconds));
+
+        let confirmed = abi::Reservation {
+            status: abi::ReservationStatus::Confirmed as i32,
+            ..rsvp.clone()
+        };
+        let err = reserve_any(confirmed).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatus(abi::ReservationStatus::Confirmed as i32)
+        );
+
+        let anonymous = abi::Reservation {
+            user_id: "".into(),
+            ..rsvp
+        };
+        let err = reserve_any(anonymous).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidUserId("".into()));
+    }
+
+    #[tokio::test]
+    async fn reserve_change_status_unknown_id_should_return_not_found() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let err = manager.change_status(10086).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        let err = manager.change_status(0).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidReservationId(0));
+    }
+
+    #[tokio::test]
+    async fn update_note_should_work() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let rsvp = manager
+            .update_note(rsvp.id, "Hello, World.".into())
+            .await
+            .unwrap();
+        assert_eq!(rsvp.note, "Hello, World.");
+    }
+
+    #[tokio::test]
+    async fn reschedule_should_move_reservation_and_record_change() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let start: Timestamp = "2024-03-01T15:00:00-0700".parse().unwrap();
+        let end: Timestamp = "2024-03-05T12:00:00-0700".parse().unwrap();
+        let moved = manager
+            .reschedule(rsvp.id, start.clone(), end.clone())
+            .await
+            .unwrap();
+        assert_eq!(moved.id, rsvp.id);
+        assert_eq!(moved.start, Some(start));
+        assert_eq!(moved.end, Some(end));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), moved);
+
+        let op: abi::RsvpUpdateType = sqlx::query_scalar(
+            "SELECT op FROM rsvp.reservation_changes WHERE reservation_id = $1 ORDER BY id DESC",
+        )
+        .bind(rsvp.id)
+        .fetch_one(&migrated_pool)
+        .await
+        .unwrap();
+        assert_eq!(op, abi::RsvpUpdateType::Update);
+    }
+
+    #[tokio::test]
+    async fn reschedule_conflict_reservation_should_reject() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (tyr, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let rsvp = abi::Reservation::new_pending(
+            "aliceid",
+            "ocean-view-room-713",
+            "2024-01-01T15:00:00-0700".parse().unwrap(),
+            "2024-01-02T12:00:00-0700".parse().unwrap(),
+            "hello.",
+        );
+        let rsvp = manager.reserve(rsvp).await.unwrap();
+
+        let err = manager
+            .reschedule(
+                rsvp.id,
+                "2023-12-26T15:00:00-0700".parse().unwrap(),
+                "2023-12-30T12:00:00-0700".parse().unwrap(),
+            )
+            .await
+            .unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: ResrvationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2023-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-30T12:00:00-0700".parse().unwrap(),
+            },
+            old: ResrvationWindow {
+                rid: tyr.resource_id.clone(),
+                start: "2023-12-25T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-28T12:00:00-0700".parse().unwrap(),
+            },
+            existing: vec![conflicting(&tyr, "", abi::ReservationStatus::Pending)],
+            saturated: vec![ResrvationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2023-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-28T12:00:00-0700".parse().unwrap(),
+            }],
+        }));
         assert_eq!(err, abi::Error::ConflictReservation(info));
 
-        // if let abi::Error::ConflictReservation(ReservationConflictInfo::Parsed(info)) = err {
-        //     assert_eq!(info.old.rid, "ocean-view-room-713");
-        //     assert_eq!(info.old.start.to_rfc3339(), "2023-12-25T22:00:00+00:00");
-        //     assert_eq!(info.old.end.to_rfc3339(), "2023-12-28T19:00:00+00:00");
-        // } else {
-        //     panic!("expect conflict reservation error");
-        // }
+        // the reservation keeps its original window
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
+
+        let err = manager
+            .reschedule(
+                rsvp.id,
+                "2024-01-02T12:00:00-0700".parse().unwrap(),
+                "2024-01-01T15:00:00-0700".parse().unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::InvalidTime);
     }
 
     #[tokio::test]
-    async fn reserve_change_status_not_pending_should_do_nothing() {
+    async fn transfer_should_move_reservation_to_another_resource() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
-        let rsvp = manager.change_status(rsvp.id).await.unwrap();
-        // change status again should do nothing
-        let ret = manager.change_status(rsvp.id).await.unwrap_err();
-        assert_eq!(ret, abi::Error::NotFound);
-        // assert_eq!(rsvp.status, abi::ReservationStatus::Confirmed as i32);
+        let moved = manager
+            .transfer(rsvp.id, "ixia-test-2".into())
+            .await
+            .unwrap();
+        assert_eq!(moved.id, rsvp.id);
+        assert_eq!(moved.resource_id, "ixia-test-2");
+        assert_eq!(moved.start, rsvp.start);
+        assert_eq!(moved.end, rsvp.end);
+
+        let err = manager.transfer(rsvp.id, "".into()).await.unwrap_err();
+        assert_eq!(err, abi::Error::InvalidResourceId("".into()));
+
+        manager
+            .deactivate_resource("ixia-test-1".into())
+            .await
+            .unwrap();
+        let err = manager
+            .transfer(rsvp.id, "ixia-test-1".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("ixia-test-1".into()));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), moved);
     }
 
     #[tokio::test]
-    async fn update_note_should_work() {
+    async fn cancelled_reservation_should_not_move() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
-        let rsvp = manager
-            .update_note(rsvp.id, "Hello, World.".into())
+        let cancelled = manager.delete(rsvp.id).await.unwrap();
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                start_id: Some(1),
+                consumer_id: "".into(),
+            })
+            .await;
+
+        let err = manager
+            .reschedule(
+                rsvp.id,
+                "2024-01-26T15:00:00-0700".parse().unwrap(),
+                "2024-01-30T12:00:00-0700".parse().unwrap(),
+            )
             .await
-            .unwrap();
-        assert_eq!(rsvp.note, "Hello, World.");
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        let err = manager
+            .transfer(rsvp.id, "ixia-test-2".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), cancelled);
+
+        // the delete stays the last change of the reservation
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Create as i32);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Delete as i32);
+        let (rsvp, _) = make_tyr_reservation(migrated_pool.clone()).await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation, Some(rsvp));
+    }
+
+    #[tokio::test]
+    async fn transfer_conflict_reservation_should_reject() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (tyr, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let (rsvp, _) = make_reservation(
+            migrated_pool.clone(),
+            "aliceid",
+            "ocean-view-room-714",
+            "2023-12-26T15:00:00-0700",
+            "2023-12-30T12:00:00-0700",
+            "hello.",
+        )
+        .await;
+
+        let err = manager
+            .transfer(rsvp.id, tyr.resource_id.clone())
+            .await
+            .unwrap_err();
+
+        let info = ReservationConflictInfo::Parsed(Box::new(ReservationConflict {
+            new: ResrvationWindow {
+                rid: tyr.resource_id.clone(),
+                start: "2023-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-30T12:00:00-0700".parse().unwrap(),
+            },
+            old: ResrvationWindow {
+                rid: tyr.resource_id.clone(),
+                start: "2023-12-25T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-28T12:00:00-0700".parse().unwrap(),
+            },
+            existing: vec![conflicting(&tyr, "", abi::ReservationStatus::Pending)],
+            saturated: vec![ResrvationWindow {
+                rid: "ocean-view-room-713".to_string(),
+                start: "2023-12-26T15:00:00-0700".parse().unwrap(),
+                end: "2023-12-28T12:00:00-0700".parse().unwrap(),
+            }],
+        }));
+        assert_eq!(err, abi::Error::ConflictReservation(info));
+        assert_eq!(manager.get(rsvp.id).await.unwrap(), rsvp);
     }
 
     #[tokio::test]
     async fn get_reservation_should_work() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
         let rsvp1 = manager.get(rsvp.id).await.unwrap();
         assert_eq!(rsvp, rsvp1);
@@ -310,17 +2743,57 @@ mod tests {
         println!("Start");
         let tdb = get_tdb();
         println!("Successful");
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
-        manager.delete(rsvp.id).await.unwrap();
-        let ret = manager.get(rsvp.id).await.unwrap_err();
-        assert_eq!(ret, abi::Error::NotFound);
+        let cancelled = manager.delete(rsvp.id).await.unwrap();
+        assert_eq!(cancelled.status, abi::ReservationStatus::Cancelled as i32);
+        // cancelled reservations are kept for history
+        let ret = manager.get(rsvp.id).await.unwrap();
+        assert_eq!(ret, cancelled);
+        // cancel again returns the reservation unchanged
+        assert_eq!(manager.delete(rsvp.id).await.unwrap(), cancelled);
+    }
+
+    #[tokio::test]
+    async fn cancelled_reservation_should_release_window_and_stay_queryable() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_tyr_reservation(migrated_pool.clone()).await;
+        let cancelled = manager.delete(rsvp.id).await.unwrap();
+
+        // the window is free again
+        let (rsvp2, _) = make_tyr_reservation(migrated_pool.clone()).await;
+        assert_ne!(rsvp2.id, rsvp.id);
+
+        let err = manager.change_status(rsvp.id).await.unwrap_err();
+        assert_eq!(
+            err,
+            abi::Error::InvalidStatusTransition(
+                abi::ReservationStatus::Cancelled,
+                abi::ReservationStatus::Confirmed
+            )
+        );
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("tyrid")
+            .status(abi::ReservationStatus::Cancelled as i32)
+            .build()
+            .unwrap();
+        assert_eq!(query_all(&manager, query).await, vec![cancelled.clone()]);
+
+        let filter = ReservationFilterBuilder::default()
+            .user_id("tyrid")
+            .status(abi::ReservationStatus::Cancelled as i32)
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![cancelled]);
     }
 
     #[tokio::test]
     async fn query_reservations_should_work() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
         // let query = ReservationQuery::new("aliceid", "ocean-view-room-713", "2022-12-26T15::00:00-0700".parse().unwrap(), "2024-12-20T12::00:00-0700".parse().unwrap(), abi::ReservationStatus::Pending, 1, 10, false);
         let query = ReservationQueryBuilder::default()
@@ -365,7 +2838,7 @@ mod tests {
     #[tokio::test]
     async fn filter_reservation_should_work() {
         let tdb = get_tdb();
-        let migrated_pool = tdb.get_pool().await;
+        let migrated_pool = get_pool(&tdb).await;
         let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
         let filter = ReservationFilterBuilder::default()
             .user_id("aliceid")
@@ -379,11 +2852,486 @@ mod tests {
         assert_eq!(rsvps[0], rsvp);
     }
 
+    #[tokio::test]
+    async fn query_and_filter_should_treat_hostile_ids_as_literals() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_reservation(
+            migrated_pool.clone(),
+            "o'neil",
+            "ixia-test-1",
+            "2024-01-25T15:00:00-0700",
+            "2024-02-25T12:00:00-0700",
+            "quote in user id",
+        )
+        .await;
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("o'neil")
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, Some(Ok(rsvp.clone())));
+        assert_eq!(rx.recv().await, None);
+
+        let query = ReservationQueryBuilder::default()
+            .user_id("x' OR '1'='1")
+            .build()
+            .unwrap();
+        let mut rx = manager.query(query).await;
+        assert_eq!(rx.recv().await, None);
+
+        let filter = ReservationFilterBuilder::default()
+            .resource_id("x' OR '1'='1")
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert!(rsvps.is_empty());
+
+        let filter = ReservationFilterBuilder::default()
+            .user_id("o'neil")
+            .build()
+            .unwrap();
+        let (_, rsvps) = manager.filter(filter).await.unwrap();
+        assert_eq!(rsvps, vec![rsvp]);
+    }
+
+    #[tokio::test]
+    async fn listen_should_receive_reservation_changes() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        let confirmed = manager.change_status(rsvp.id).await.unwrap();
+        let cancelled = manager.delete(rsvp.id).await.unwrap();
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Create as i32);
+        assert_eq!(change.reservation, Some(rsvp.clone()));
+        assert_eq!(change.old, None);
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(confirmed.clone()));
+        assert_eq!(change.old, Some(rsvp.clone()));
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Delete as i32);
+        assert_eq!(change.reservation, Some(cancelled));
+        assert_eq!(change.old, Some(confirmed));
+    }
+
+    #[tokio::test]
+    async fn listen_should_not_lose_changes_committed_out_of_order() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        // the first change gets the lower id but commits last
+        let mut tx = migrated_pool.begin().await.unwrap();
+        let first = abi::Reservation::new_pending(
+            "aliceid",
+            "room-1",
+            "2024-03-01T10:00:00Z".parse().unwrap(),
+            "2024-03-01T12:00:00Z".parse().unwrap(),
+            "first",
+        );
+        let first = insert_reservation(&mut *tx, first, None).await.unwrap();
+        let (second, _) = make_tyr_reservation(migrated_pool.clone()).await;
+        time::sleep(Duration::from_millis(200)).await;
+        tx.commit().await.unwrap();
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation.unwrap().id, first.id);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation.unwrap().id, second.id);
+    }
+
+    #[tokio::test]
+    async fn listen_should_not_depend_on_session_date_style() {
+        let tdb = get_tdb();
+        get_pool(&tdb).await;
+        let pool = PgPoolOptions::new()
+            .after_connect(|conn, _| {
+                Box::pin(async move {
+                    sqlx::query("SET DateStyle = 'SQL, DMY'")
+                        .execute(&mut *conn)
+                        .await?;
+                    sqlx::query("SET TimeZone = 'Asia/Tokyo'")
+                        .execute(&mut *conn)
+                        .await?;
+                    Ok(())
+                })
+            })
+            .connect(&tdb.url())
+            .await
+            .unwrap();
+        let manager = ReservationManager::new(pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        let (rsvp, _) = make_alice_reservation(pool).await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.reservation, Some(rsvp));
+    }
+
+    #[tokio::test]
+    async fn listen_should_skip_changes_it_cannot_decode() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        sqlx::query(
+            "INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) \
+            VALUES (1, NULL, '{\"id\": \"not a number\"}', 'create')",
+        )
+        .execute(&migrated_pool)
+        .await
+        .unwrap();
+        let (rsvp, _) = make_alice_reservation(migrated_pool.clone()).await;
+
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Create as i32);
+        assert_eq!(change.reservation, Some(rsvp));
+    }
+
+    #[tokio::test]
+    async fn listen_should_receive_flagged_reservations() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let rsvp = manager
+            .reserve(batch_reservations(&["room-1"]).remove(0))
+            .await
+            .unwrap();
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+
+        let resp = manager
+            .blackout(lab_blackout("room-1"), abi::BlackoutPolicy::FlagPending)
+            .await
+            .unwrap();
+        let flagged = resp.affected[0].clone();
+        assert!(flagged.flagged);
+
+        // the blackout is made flagged and unflagged around the flagged reservation
+        let blackout_id = resp.blackout.unwrap().id;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Create as i32);
+        assert_eq!(change.reservation.unwrap().id, blackout_id);
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation, Some(flagged));
+        assert_eq!(change.old, Some(rsvp));
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.op, abi::ReservationUpdateType::Update as i32);
+        assert_eq!(change.reservation.unwrap().id, blackout_id);
+    }
+
+    #[tokio::test]
+    async fn cancel_should_promote_waitlist_in_order() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let mut rx = manager.listen(abi::ListenRequest::default()).await;
+        let at = |hour: u32| format!("2099-01-01T{:02}:00:00Z", hour).parse().unwrap();
+
+        let alice = manager
+            .reserve(abi::Reservation::new_pending(
+                "aliceid",
+                "room-1",
+                at(10),
+                at(12),
+                "standup",
+            ))
+            .await
+            .unwrap();
+        let bob = abi::WaitlistEntry::new("bobid", "room-1", at(10), at(12), "retro");
+        let bob = manager.join_waitlist(bob).await.unwrap();
+        let carol = abi::WaitlistEntry::new("carolid", "room-1", at(11), at(12), "1:1");
+        let carol = manager.join_waitlist(carol).await.unwrap();
+        // joining again keeps the place in the queue
+        let again = abi::WaitlistEntry::new("bobid", "room-1", at(10), at(12), "planning");
+        let again = manager.join_waitlist(again).await.unwrap();
+        assert_eq!(again.id, bob.id);
+        assert_eq!(again.note, "planning");
+
+        let err = manager
+            .join_waitlist(abi::WaitlistEntry::new(
+                "bobid",
+                "room-9",
+                at(10),
+                at(12),
+                "",
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::UnknownResource("room-9".into()));
+
+        // a free window is reserved, not waited for
+        let err = manager
+            .join_waitlist(abi::WaitlistEntry::new(
+                "bobid",
+                "room-1",
+                at(12),
+                at(13),
+                "",
+            ))
+            .await
+            .unwrap_err();
+        assert_eq!(err, abi::Error::WindowNotTaken("room-1".into()));
+
+        let query = abi::ListWaitlistRequest::new(
+            "room-1",
+            convert_to_timestamp(&at(11).into()),
+            convert_to_timestamp(&at(13).into()),
+        );
+        let entries = manager.list_waitlist(query.clone()).await.unwrap();
+        assert_eq!(entries, vec![again, carol.clone()]);
+
+        // bob joined first and gets the window, carol still conflicts with him
+        manager.delete(alice.id).await.unwrap();
+        let promoted = manager
+            .filter(
+                ReservationFilterBuilder::default()
+                    .user_id("bobid")
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].resource_id, "room-1");
+        assert_eq!(promoted[0].status, abi::ReservationStatus::Pending as i32);
+        assert_eq!(promoted[0].note, "planning");
+        let entries = manager.list_waitlist(query).await.unwrap();
+        assert_eq!(entries, vec![carol.clone()]);
+
+        // the promotion is reported after the cancellation
+        use abi::ReservationUpdateType::{Create, Delete};
+        for (op, id) in [
+            (Create, alice.id),
+            (Delete, alice.id),
+            (Create, promoted[0].id),
+        ] {
+            let change = rx.recv().await.unwrap().unwrap();
+            assert_eq!(change.op, op as i32);
+            assert_eq!(change.reservation.unwrap().id, id);
+        }
+
+        let left = manager.leave_waitlist(carol.id).await.unwrap();
+        assert_eq!(left, carol);
+        let err = manager.leave_waitlist(carol.id).await.unwrap_err();
+        assert_eq!(err, abi::Error::NotFound);
+    }
+
+    #[tokio::test]
+    async fn cancel_should_not_promote_waitlist_breaking_booking_rules() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let at = |hour: u32| format!("2099-01-01T{:02}:00:00Z", hour).parse().unwrap();
+
+        let alice = manager
+            .reserve(abi::Reservation::new_pending(
+                "aliceid",
+                "room-1",
+                at(10),
+                at(12),
+                "standup",
+            ))
+            .await
+            .unwrap();
+        let bob = abi::WaitlistEntry::new("bobid", "room-1", at(10), at(12), "retro");
+        let bob = manager.join_waitlist(bob).await.unwrap();
+        let carol = abi::WaitlistEntry::new("carolid", "room-1", at(11), at(12), "1:1");
+        manager.join_waitlist(carol).await.unwrap();
+
+        // the room opens at 11:30 in Paris from now on, which is 10:30 UTC in winter
+        let rules = abi::BookingRules {
+            timezone: "Europe/Paris".into(),
+            opening_hours: (1..=7)
+                .map(|weekday| abi::OpeningHours::new(weekday, "11:30", "18:00").unwrap())
+                .collect(),
+            ..abi::BookingRules::new("room-1")
+        };
+        manager.set_booking_rules(rules).await.unwrap();
+
+        // bob starts before the room opens and keeps waiting, carol gets the window
+        manager.delete(alice.id).await.unwrap();
+        let promoted = manager
+            .filter(ReservationFilterBuilder::default().build().unwrap())
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].user_id, "carolid");
+        let query = abi::ListWaitlistRequest::new(
+            "room-1",
+            convert_to_timestamp(&at(9).into()),
+            convert_to_timestamp(&at(15).into()),
+        );
+        let entries = manager.list_waitlist(query).await.unwrap();
+        assert_eq!(entries, vec![bob]);
+    }
+
+    #[tokio::test]
+    async fn blackout_cancelling_pending_should_not_promote_waitlist_into_it() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let at = |hour: u32| format!("2099-01-01T{:02}:00:00Z", hour).parse().unwrap();
+
+        let alice = manager
+            .reserve(abi::Reservation::new_pending(
+                "aliceid",
+                "room-1",
+                at(10),
+                at(14),
+                "workshop",
+            ))
+            .await
+            .unwrap();
+        // bob waits inside the blackout, carol next to it
+        let bob = abi::WaitlistEntry::new("bobid", "room-1", at(10), at(12), "retro");
+        let bob = manager.join_waitlist(bob).await.unwrap();
+        let carol = abi::WaitlistEntry::new("carolid", "room-1", at(13), at(14), "1:1");
+        manager.join_waitlist(carol).await.unwrap();
+
+        let blackout = abi::Reservation::new_blackout("room-1", at(9), at(12), "under repair");
+        let resp = manager
+            .blackout(blackout, abi::BlackoutPolicy::CancelPending)
+            .await
+            .unwrap();
+        assert!(resp.blackout.unwrap().is_blackout());
+        assert_eq!(resp.affected.len(), 1);
+        assert_eq!(resp.affected[0].id, alice.id);
+
+        let promoted = manager
+            .filter(ReservationFilterBuilder::default().build().unwrap())
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(promoted.len(), 1);
+        assert_eq!(promoted[0].user_id, "carolid");
+        let query = abi::ListWaitlistRequest::new(
+            "room-1",
+            convert_to_timestamp(&at(9).into()),
+            convert_to_timestamp(&at(15).into()),
+        );
+        let entries = manager.list_waitlist(query).await.unwrap();
+        assert_eq!(entries, vec![bob]);
+    }
+
+    #[tokio::test]
+    async fn listen_should_resume_from_acknowledged_position() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let manager = ReservationManager::new(migrated_pool.clone());
+        let req = abi::ListenRequest {
+            start_id: None,
+            consumer_id: "billing".into(),
+        };
+        let mut rx = manager.listen(req.clone()).await;
+
+        make_alice_reservation(migrated_pool.clone()).await;
+        make_tyr_reservation(migrated_pool.clone()).await;
+
+        let first = rx.recv().await.unwrap().unwrap();
+        let acked = manager
+            .ack(abi::AckRequest::new("billing", first.id))
+            .await
+            .unwrap();
+        assert_eq!(acked, first.id);
+        drop(rx);
+
+        // the unacknowledged change is replayed after reconnecting
+        let mut rx = manager.listen(req).await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.id, first.id + 1);
+        assert_eq!(change.reservation.unwrap().user_id, "tyrid");
+
+        // acknowledging an older change doesn't move the position backwards
+        let acked = manager
+            .ack(abi::AckRequest::new("billing", first.id - 1))
+            .await
+            .unwrap();
+        assert_eq!(acked, first.id);
+
+        // start_id replays from the given change regardless of consumer
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                start_id: Some(first.id),
+                consumer_id: "".into(),
+            })
+            .await;
+        let change = rx.recv().await.unwrap().unwrap();
+        assert_eq!(change.id, first.id);
+        assert_eq!(change.reservation.unwrap().user_id, "aliceid");
+    }
+
+    #[tokio::test]
+    async fn listen_should_replay_a_backlog_larger_than_a_batch() {
+        let tdb = get_tdb();
+        let migrated_pool = get_pool(&tdb).await;
+        let (rsvp, manager) = make_alice_reservation(migrated_pool.clone()).await;
+        // copies of the create change, no notification is sent for them
+        sqlx::query(
+            "INSERT INTO rsvp.reservation_changes (reservation_id, old, new, op) \
+            SELECT reservation_id, old, new, op FROM rsvp.reservation_changes, generate_series(1, $1)",
+        )
+        .bind(CHANGE_BATCH_SIZE)
+        .execute(&migrated_pool)
+        .await
+        .unwrap();
+
+        let mut rx = manager
+            .listen(abi::ListenRequest {
+                start_id: Some(1),
+                consumer_id: "".into(),
+            })
+            .await;
+        for id in 1..=CHANGE_BATCH_SIZE + 1 {
+            let change = rx.recv().await.unwrap().unwrap();
+            assert_eq!(change.id, id);
+            assert_eq!(change.reservation.as_ref(), Some(&rsvp));
+        }
+    }
+
     // private none test functions
-    fn get_tdb() -> TestDb {
+    pub(crate) fn get_tdb() -> TestDb {
         TestDb::new("localhost", 5432, "postgres", "postgres", "../migrations")
     }
 
+    /// every resource the tests reserve on, reservations need a catalog entry
+    const TEST_RESOURCES: &[&str] = &[
+        "ocean-view-room-713",
+        "ocean-view-room-714",
+        "ixia-test-1",
+        "ixia-test-2",
+        "projector-1",
+        "rig-1",
+        "room-1",
+        "room-2",
+        "room-3",
+        "room-4",
+    ];
+
+    async fn get_pool(tdb: &TestDb) -> PgPool {
+        let pool = tdb.get_pool().await;
+        sqlx::query(
+            "INSERT INTO rsvp.resources (id, name) SELECT id, id FROM unnest($1::text[]) AS id",
+        )
+        .bind(TEST_RESOURCES)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
     async fn make_tyr_reservation(pool: PgPool) -> (Reservation, ReservationManager) {
         make_reservation(
             pool,
@@ -408,6 +3356,82 @@ mod tests {
         .await
     }
 
+    fn conflicting(
+        rsvp: &Reservation,
+        user_id: &str,
+        status: abi::ReservationStatus,
+    ) -> abi::ConflictingReservation {
+        abi::ConflictingReservation {
+            id: rsvp.id,
+            user_id: user_id.into(),
+            status: status as i32,
+            resource_id: rsvp.resource_id.clone(),
+        }
+    }
+
+    fn batch_reservations(rids: &[&str]) -> Vec<Reservation> {
+        rids.iter()
+            .map(|rid| {
+                abi::Reservation::new_pending(
+                    "aliceid",
+                    *rid,
+                    "2023-12-26T15:00:00-0700".parse().unwrap(),
+                    "2023-12-30T12:00:00-0700".parse().unwrap(),
+                    "weekly review",
+                )
+            })
+            .collect()
+    }
+
+    fn weekly_reservation() -> Reservation {
+        abi::Reservation::new_pending(
+            "aliceid",
+            "ocean-view-room-713",
+            "2023-12-18T16:00:00-0700".parse().unwrap(),
+            "2023-12-18T17:00:00-0700".parse().unwrap(),
+            "weekly sync",
+        )
+    }
+
+    fn weekly_rule(count: i32) -> abi::RecurrenceRule {
+        abi::RecurrenceRule {
+            frequency: abi::RecurrenceFrequency::Weekly as i32,
+            interval: 1,
+            count,
+            until: None,
+            exceptions: vec![],
+        }
+    }
+
+    fn lab_blackout(rid: &str) -> Reservation {
+        abi::Reservation::new_blackout(
+            rid,
+            "2023-12-27T00:00:00-0700".parse().unwrap(),
+            "2023-12-28T00:00:00-0700".parse().unwrap(),
+            "under repair",
+        )
+    }
+
+    async fn query_all(
+        manager: &ReservationManager,
+        query: abi::ReservationQuery,
+    ) -> Vec<Reservation> {
+        let mut rx = manager.query(query).await;
+        let mut rsvps = vec![];
+        while let Some(rsvp) = rx.recv().await {
+            rsvps.push(rsvp.unwrap());
+        }
+        rsvps
+    }
+
+    fn free_slot(rid: &str, start: &str, end: &str) -> (String, Timestamp, Timestamp) {
+        (
+            rid.to_string(),
+            start.parse().unwrap(),
+            end.parse().unwrap(),
+        )
+    }
+
     async fn make_reservation(
         pool: PgPool,
         uid: &str,
@@ -430,5 +3454,5 @@ mod tests {
 }
 
 // let tdb = get_tdb();
-// let migrated_pool = tdb.get_pool().await;
+// let migrated_pool = get_pool(&tdb).await;
 // 为什么不把367和368类似的语句合并，tdb能工作依赖于tdb一直在每个函数内部，如果合并到一块，tdb会在合并函数结束时推出，然后导致connection在做操作时找不到database。
@@ -1,22 +1,30 @@
+mod resource;
 mod service;
 // #[cfg(feature = "test-utils")]
 // mod test_utils;
 #[cfg(test)]
 pub mod test_utils;
 
-use std::{pin::Pin, task::Poll};
+use std::{pin::Pin, task::Poll, time::Duration};
 
-use abi::{Config, Reservation, reservation_service_server::ReservationServiceServer};
+use abi::{
+    reservation_service_server::ReservationServiceServer,
+    resource_service_server::ResourceServiceServer, Config, ListenResponse, Reservation,
+    ReservationId,
+};
 use futures::Stream;
-use reservation::ReservationManager;
-use tokio::sync::mpsc;
-use tonic::{Status, transport::Server};
+use reservation::{ReservationManager, Rsvp};
+use tokio::{sync::mpsc, time};
+use tonic::{transport::Server, Request, Status};
+use tracing::{info, warn};
 
 type ReservationStream = Pin<Box<dyn Stream<Item = Result<Reservation, Status>> + Send>>;
+type ListenStream = Pin<Box<dyn Stream<Item = Result<ListenResponse, Status>> + Send>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RsvpService {
     pub manager: ReservationManager,
+    admin_token: Option<String>,
 }
 
 pub struct TonicReceiverStream<T> {
@@ -33,8 +41,34 @@ impl RsvpService {
     pub async fn from_config(config: &Config) -> Result<Self, anyhow::Error> {
         Ok(Self {
             manager: ReservationManager::from_config(&config.db).await?,
+            admin_token: config.server.admin_token.clone(),
         })
     }
+
+    /// admin rpcs need the `authorization: Bearer <admin_token>` metadata
+    fn check_admin<T>(&self, request: &Request<T>) -> Result<(), abi::Error> {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        match (self.admin_token.as_deref(), token) {
+            (Some(expected), Some(token)) if expected == token => Ok(()),
+            _ => Err(abi::Error::PermissionDenied),
+        }
+    }
+
+    /// changing a blackout takes the same admin token as making it
+    async fn check_blackout_admin<T>(
+        &self,
+        request: &Request<T>,
+        id: ReservationId,
+    ) -> Result<(), abi::Error> {
+        if self.manager.get(id).await?.is_blackout() {
+            self.check_admin(request)?;
+        }
+        Ok(())
+    }
 }
 
 impl<T> Stream for TonicReceiverStream<T> {
@@ -54,9 +88,34 @@ pub async fn start_server(config: &Config) -> Result<(), anyhow::Error> {
     let addr = format!("{}:{}", config.server.host, config.server.port).parse()?;
 
     let svc = RsvpService::from_config(config).await?;
+    if config.reaper.interval > 0 {
+        let interval = Duration::from_secs(config.reaper.interval);
+        tokio::spawn(reap_expired_holds(svc.manager.clone(), interval));
+    }
+    // both services share the same manager
+    let resources = ResourceServiceServer::new(svc.clone());
     let svc = ReservationServiceServer::new(svc);
     
     println!("Listening on {}", addr);
-    Server::builder().add_service(svc).serve(addr).await?;
+    Server::builder()
+        .add_service(svc)
+        .add_service(resources)
+        .serve(addr)
+        .await?;
     Ok(())
 }
+
+/// periodically cancel pending reservations whose hold has expired
+async fn reap_expired_holds(manager: ReservationManager, interval: Duration) {
+    let mut ticker = time::interval(interval);
+    loop {
+        ticker.tick().await;
+        match manager.reap_expired().await {
+            Ok(reaped) if !reaped.is_empty() => {
+                info!("reaped {} expired pending reservations", reaped.len())
+            }
+            Ok(_) => {}
+            Err(e) => warn!("failed to reap expired pending reservations: {}", e),
+        }
+    }
+}
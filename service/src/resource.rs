@@ -0,0 +1,256 @@
+use abi::{
+    resource_service_server::ResourceService, CreateResourceGroupRequest,
+    CreateResourceGroupResponse, CreateResourceRequest, CreateResourceResponse,
+    DeactivateResourceRequest, DeactivateResourceResponse, GetBookingRulesRequest,
+    GetBookingRulesResponse, GetResourceGroupRequest, GetResourceGroupResponse, GetResourceRequest,
+    GetResourceResponse, ListResourceGroupsRequest, ListResourceGroupsResponse,
+    ListResourcesRequest, ListResourcesResponse, SetBookingRulesRequest, SetBookingRulesResponse,
+    UpdateResourceGroupRequest, UpdateResourceGroupResponse, UpdateResourceRequest,
+    UpdateResourceResponse,
+};
+use reservation::Catalog;
+use tonic::{async_trait, Request, Response, Status};
+
+use crate::RsvpService;
+
+#[async_trait]
+impl ResourceService for RsvpService {
+    /// add a resource to the catalog
+    async fn create_resource(
+        &self,
+        request: Request<CreateResourceRequest>,
+    ) -> Result<Response<CreateResourceResponse>, Status> {
+        let resource = request
+            .into_inner()
+            .resource
+            .ok_or_else(|| Status::invalid_argument("missing resource"))?;
+        let resource = self.manager.create_resource(resource).await?;
+        Ok(Response::new(CreateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// update a resource, the id can't be changed
+    async fn update_resource(
+        &self,
+        request: Request<UpdateResourceRequest>,
+    ) -> Result<Response<UpdateResourceResponse>, Status> {
+        let resource = request
+            .into_inner()
+            .resource
+            .ok_or_else(|| Status::invalid_argument("missing resource"))?;
+        let resource = self.manager.update_resource(resource).await?;
+        Ok(Response::new(UpdateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// get a resource by id
+    async fn get_resource(
+        &self,
+        request: Request<GetResourceRequest>,
+    ) -> Result<Response<GetResourceResponse>, Status> {
+        let resource = self.manager.get_resource(request.into_inner().id).await?;
+        Ok(Response::new(GetResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// list the resources of the catalog
+    async fn list_resources(
+        &self,
+        request: Request<ListResourcesRequest>,
+    ) -> Result<Response<ListResourcesResponse>, Status> {
+        let resources = self.manager.list_resources(request.into_inner()).await?;
+        Ok(Response::new(ListResourcesResponse { resources }))
+    }
+
+    /// stop accepting reservations on a resource, existing reservations are kept
+    async fn deactivate_resource(
+        &self,
+        request: Request<DeactivateResourceRequest>,
+    ) -> Result<Response<DeactivateResourceResponse>, Status> {
+        let resource = self
+            .manager
+            .deactivate_resource(request.into_inner().id)
+            .await?;
+        Ok(Response::new(DeactivateResourceResponse {
+            resource: Some(resource),
+        }))
+    }
+
+    /// replace the booking rules of a resource
+    async fn set_booking_rules(
+        &self,
+        request: Request<SetBookingRulesRequest>,
+    ) -> Result<Response<SetBookingRulesResponse>, Status> {
+        let rules = request
+            .into_inner()
+            .rules
+            .ok_or_else(|| Status::invalid_argument("missing rules"))?;
+        let rules = self.manager.set_booking_rules(rules).await?;
+        Ok(Response::new(SetBookingRulesResponse {
+            rules: Some(rules),
+        }))
+    }
+
+    /// get the booking rules of a resource
+    async fn get_booking_rules(
+        &self,
+        request: Request<GetBookingRulesRequest>,
+    ) -> Result<Response<GetBookingRulesResponse>, Status> {
+        let rules = self
+            .manager
+            .get_booking_rules(request.into_inner().resource_id)
+            .await?;
+        Ok(Response::new(GetBookingRulesResponse {
+            rules: Some(rules),
+        }))
+    }
+
+    /// add a group of resources for reserve_any
+    async fn create_resource_group(
+        &self,
+        request: Request<CreateResourceGroupRequest>,
+    ) -> Result<Response<CreateResourceGroupResponse>, Status> {
+        let group = request
+            .into_inner()
+            .group
+            .ok_or_else(|| Status::invalid_argument("missing group"))?;
+        let group = self.manager.create_resource_group(group).await?;
+        Ok(Response::new(CreateResourceGroupResponse {
+            group: Some(group),
+        }))
+    }
+
+    /// replace the name and members of a group
+    async fn update_resource_group(
+        &self,
+        request: Request<UpdateResourceGroupRequest>,
+    ) -> Result<Response<UpdateResourceGroupResponse>, Status> {
+        let group = request
+            .into_inner()
+            .group
+            .ok_or_else(|| Status::invalid_argument("missing group"))?;
+        let group = self.manager.update_resource_group(group).await?;
+        Ok(Response::new(UpdateResourceGroupResponse {
+            group: Some(group),
+        }))
+    }
+
+    /// get a group by id
+    async fn get_resource_group(
+        &self,
+        request: Request<GetResourceGroupRequest>,
+    ) -> Result<Response<GetResourceGroupResponse>, Status> {
+        let group = self
+            .manager
+            .get_resource_group(request.into_inner().id)
+            .await?;
+        Ok(Response::new(GetResourceGroupResponse {
+            group: Some(group),
+        }))
+    }
+
+    /// list the groups of the catalog
+    async fn list_resource_groups(
+        &self,
+        _request: Request<ListResourceGroupsRequest>,
+    ) -> Result<Response<ListResourceGroupsResponse>, Status> {
+        let groups = self.manager.list_resource_groups().await?;
+        Ok(Response::new(ListResourceGroupsResponse { groups }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestConfig;
+    use abi::{
+        reservation_service_server::ReservationService, BookingRules, PolicyViolationDetail,
+        PolicyViolationReason, Reservation, ReserveRequest, Resource, StatusDetail,
+    };
+
+    #[tokio::test]
+    async fn rpc_reserve_on_deactivated_resource_should_fail() {
+        let config = TestConfig::new();
+        let service = RsvpService::from_config(&config).await.unwrap();
+
+        let request = CreateResourceRequest::new(Resource::new("ixia-3230", "Ixia 3230", "device"));
+        let resource = service
+            .create_resource(Request::new(request))
+            .await
+            .unwrap()
+            .into_inner()
+            .resource
+            .unwrap();
+        assert!(resource.active());
+
+        let request = DeactivateResourceRequest::new("ixia-3230");
+        service
+            .deactivate_resource(Request::new(request))
+            .await
+            .unwrap();
+
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-30T12:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let status = service
+            .reserve(Request::new(ReserveRequest::new(reservation)))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let status = service
+            .get_resource(Request::new(GetResourceRequest::new("ixia-9999")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn rpc_reserve_breaking_booking_rules_should_fail() {
+        let config = TestConfig::new();
+        let service = RsvpService::from_config(&config).await.unwrap();
+
+        let request = CreateResourceRequest::new(Resource::new("ixia-3230", "Ixia 3230", "device"));
+        service
+            .create_resource(Request::new(request))
+            .await
+            .unwrap();
+        let rules = BookingRules {
+            max_duration: 3600,
+            ..BookingRules::new("ixia-3230")
+        };
+        service
+            .set_booking_rules(Request::new(SetBookingRulesRequest::new(rules.clone())))
+            .await
+            .unwrap();
+        let stored = service
+            .get_booking_rules(Request::new(GetBookingRulesRequest::new("ixia-3230")))
+            .await
+            .unwrap()
+            .into_inner()
+            .rules;
+        assert_eq!(stored, Some(rules));
+
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T17:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let status = service
+            .reserve(Request::new(ReserveRequest::new(reservation)))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        let detail = PolicyViolationDetail::from_status(&status).unwrap();
+        assert_eq!(detail.reason(), PolicyViolationReason::TooLong);
+    }
+}
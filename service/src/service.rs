@@ -1,13 +1,22 @@
 use abi::{
-    reservation_service_server::ReservationService, CancelRequest, CancelResponse, ConfirmRequest,
-    ConfirmResponse, FilterRequest, FilterResponse, GetRequest, GetResponse, ListenRequest,
-    QueryRequest, ReserveRequest, ReserveResponse, UpdateRequest, UpdateResponse,
+    reservation_service_server::ReservationService, AckRequest, AckResponse, AvailabilityRequest,
+    AvailabilityResponse, BlackoutPolicy, BlackoutRequest, BlackoutResponse, CancelRequest,
+    CancelResponse, CancelSeriesRequest, CancelSeriesResponse, ConfirmRequest, ConfirmResponse,
+    FilterRequest, FilterResponse, GetRequest, GetResponse, JoinWaitlistRequest,
+    JoinWaitlistResponse, LeaveWaitlistRequest, LeaveWaitlistResponse, ListWaitlistRequest,
+    ListWaitlistResponse, ListenRequest, QueryRequest, RescheduleRequest, RescheduleResponse,
+    Reservation, ReservationConflictDetail, ReservationStatus, ReserveAnyRequest,
+    ReserveAnyResponse, ReserveBatchRequest, ReserveBatchResponse, ReserveRecurringRequest,
+    ReserveRecurringResponse, ReserveRequest, ReserveResponse, SelectionStrategy, StatusDetail,
+    TransferRequest, TransferResponse, TransitionRequest, TransitionResponse, UpdateRequest,
+    UpdateResponse, UpdateSeriesRequest, UpdateSeriesResponse,
 };
 
 use reservation::Rsvp;
 use tonic::{async_trait, Request, Response, Status};
+use tracing::warn;
 
-use crate::{ReservationStream, RsvpService, TonicReceiverStream};
+use crate::{ListenStream, ReservationStream, RsvpService, TonicReceiverStream};
 
 #[async_trait]
 impl ReservationService for RsvpService {
@@ -21,14 +30,85 @@ impl ReservationService for RsvpService {
             return Err(Status::invalid_argument("missing reservation"));
         }
 
-        let reservation = self.manager.reserve(request.reservation.unwrap()).await?;
+        let mut reservation = request.reservation.unwrap();
+        if request.hold_ttl != 0 {
+            reservation.hold_for(request.hold_ttl)?;
+        }
+
+        let reservation = match self.manager.reserve(reservation.clone()).await {
+            Ok(reservation) => reservation,
+            Err(abi::Error::ConflictReservation(info)) if request.suggestions > 0 => {
+                let mut detail = ReservationConflictDetail::from(&info);
+                // suggestions are best effort, the conflict is returned without them if they fail
+                match self
+                    .manager
+                    .suggest_windows(
+                        reservation,
+                        request.sibling_resource_ids,
+                        request.suggestions as usize,
+                    )
+                    .await
+                {
+                    Ok(suggestions) => detail.suggestions = suggestions,
+                    Err(e) => warn!("failed to suggest windows for a conflict: {}", e),
+                }
+                let status = abi::Error::ConflictReservation(info).into();
+                return Err(detail.attach_to(status));
+            }
+            Err(e) => return Err(e.into()),
+        };
 
         Ok(Response::new(ReserveResponse {
             reservation: Some(reservation),
         }))
     }
 
-    /// confirm a pending reservation, if reservation is not pending, do nothing
+    /// make several reservations in one transaction, if any of them fails, none is made
+    async fn reserve_batch(
+        &self,
+        request: Request<ReserveBatchRequest>,
+    ) -> Result<Response<ReserveBatchResponse>, Status> {
+        let request = request.into_inner();
+        let reservations = self.manager.reserve_many(request.reservations).await?;
+        Ok(Response::new(ReserveBatchResponse { reservations }))
+    }
+
+    /// make a reservation on the first free resource of a group
+    async fn reserve_any(
+        &self,
+        request: Request<ReserveAnyRequest>,
+    ) -> Result<Response<ReserveAnyResponse>, Status> {
+        let request = request.into_inner();
+        let reservation = request
+            .reservation
+            .ok_or_else(|| Status::invalid_argument("missing reservation"))?;
+        let strategy = SelectionStrategy::from_i32(request.strategy)
+            .ok_or_else(|| Status::invalid_argument("unknown selection strategy"))?;
+
+        let reservation = self
+            .manager
+            .reserve_any(reservation, request.group_id, strategy)
+            .await?;
+        Ok(Response::new(ReserveAnyResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    /// make a recurring reservation, occurrences conflicting with existing reservations are skipped
+    async fn reserve_recurring(
+        &self,
+        request: Request<ReserveRecurringRequest>,
+    ) -> Result<Response<ReserveRecurringResponse>, Status> {
+        let request = request.into_inner();
+        let (Some(reservation), Some(rule)) = (request.reservation, request.rule) else {
+            return Err(Status::invalid_argument("missing reservation or rule"));
+        };
+
+        let response = self.manager.reserve_recurring(reservation, rule).await?;
+        Ok(Response::new(response))
+    }
+
+    /// confirm a pending reservation, a confirmed reservation is returned unchanged
     async fn confirm(
         &self,
         request: Request<ConfirmRequest>,
@@ -40,6 +120,22 @@ impl ReservationService for RsvpService {
         }))
     }
 
+    /// move a reservation to another status, illegal transitions fail with FAILED_PRECONDITION
+    async fn transition(
+        &self,
+        request: Request<TransitionRequest>,
+    ) -> Result<Response<TransitionResponse>, Status> {
+        self.check_blackout_admin(&request, request.get_ref().id)
+            .await?;
+        let request = request.into_inner();
+        let status = ReservationStatus::from_i32(request.status)
+            .ok_or(abi::Error::InvalidStatus(request.status))?;
+        let reservation = self.manager.transition(request.id, status).await?;
+        Ok(Response::new(TransitionResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
     /// update the reservation note
     async fn update(
         &self,
@@ -52,11 +148,51 @@ impl ReservationService for RsvpService {
         }))
     }
 
+    /// move a reservation to another time window, keeping its id
+    async fn reschedule(
+        &self,
+        request: Request<RescheduleRequest>,
+    ) -> Result<Response<RescheduleResponse>, Status> {
+        // same error as validate_range() for a missing bound
+        let (Some(start), Some(end)) = (
+            request.get_ref().start.clone(),
+            request.get_ref().end.clone(),
+        ) else {
+            return Err(abi::Error::InvalidTime.into());
+        };
+        self.check_blackout_admin(&request, request.get_ref().id)
+            .await?;
+        let request = request.into_inner();
+        let reservation = self.manager.reschedule(request.id, start, end).await?;
+        Ok(Response::new(RescheduleResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
+    /// move a reservation to another resource, keeping its id and time window
+    async fn transfer(
+        &self,
+        request: Request<TransferRequest>,
+    ) -> Result<Response<TransferResponse>, Status> {
+        self.check_blackout_admin(&request, request.get_ref().id)
+            .await?;
+        let request = request.into_inner();
+        let reservation = self
+            .manager
+            .transfer(request.id, request.resource_id)
+            .await?;
+        Ok(Response::new(TransferResponse {
+            reservation: Some(reservation),
+        }))
+    }
+
     /// cancel a reservation
     async fn cancel(
         &self,
         request: Request<CancelRequest>,
     ) -> std::result::Result<Response<CancelResponse>, Status> {
+        self.check_blackout_admin(&request, request.get_ref().id)
+            .await?;
         let request = request.into_inner();
         let reservation = self.manager.delete(request.id).await?;
         Ok(Response::new(CancelResponse{
@@ -64,6 +200,92 @@ impl ReservationService for RsvpService {
         }))
     }
 
+    /// cancel all the occurrences of a recurring reservation, use cancel for a single occurrence
+    async fn cancel_series(
+        &self,
+        request: Request<CancelSeriesRequest>,
+    ) -> Result<Response<CancelSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let reservations = self.manager.cancel_series(request.series_id).await?;
+        Ok(Response::new(CancelSeriesResponse { reservations }))
+    }
+
+    /// update the note of all the occurrences of a recurring reservation, or move them
+    async fn update_series(
+        &self,
+        request: Request<UpdateSeriesRequest>,
+    ) -> Result<Response<UpdateSeriesResponse>, Status> {
+        let request = request.into_inner();
+        let reservations = self
+            .manager
+            .update_series(request.series_id, request.note, request.shift)
+            .await?;
+        Ok(Response::new(UpdateSeriesResponse { reservations }))
+    }
+
+    /// admin only: take a resource offline for a window for all users
+    async fn blackout(
+        &self,
+        request: Request<BlackoutRequest>,
+    ) -> Result<Response<BlackoutResponse>, Status> {
+        self.check_admin(&request)?;
+        let request = request.into_inner();
+        let policy = BlackoutPolicy::from_i32(request.policy)
+            .ok_or_else(|| Status::invalid_argument("unknown blackout policy"))?;
+        let rsvp = Reservation {
+            resource_id: request.resource_id,
+            start: request.start,
+            end: request.end,
+            note: request.note,
+            status: ReservationStatus::Blocked as i32,
+            ..Default::default()
+        };
+
+        let response = self.manager.blackout(rsvp, policy).await?;
+        Ok(Response::new(response))
+    }
+
+    /// find the free slots of resources in a window
+    async fn availability(
+        &self,
+        request: Request<AvailabilityRequest>,
+    ) -> Result<Response<AvailabilityResponse>, Status> {
+        let request = request.into_inner();
+        let slots = self.manager.find_free_slots(request).await?;
+        Ok(Response::new(AvailabilityResponse { slots }))
+    }
+
+    /// queue for a taken window, the entry is promoted to a pending reservation once it is freed
+    async fn join_waitlist(
+        &self,
+        request: Request<JoinWaitlistRequest>,
+    ) -> Result<Response<JoinWaitlistResponse>, Status> {
+        let entry = request
+            .into_inner()
+            .entry
+            .ok_or_else(|| Status::invalid_argument("missing entry"))?;
+        let entry = self.manager.join_waitlist(entry).await?;
+        Ok(Response::new(JoinWaitlistResponse { entry: Some(entry) }))
+    }
+
+    /// leave a waitlist, the removed entry is returned
+    async fn leave_waitlist(
+        &self,
+        request: Request<LeaveWaitlistRequest>,
+    ) -> Result<Response<LeaveWaitlistResponse>, Status> {
+        let entry = self.manager.leave_waitlist(request.into_inner().id).await?;
+        Ok(Response::new(LeaveWaitlistResponse { entry: Some(entry) }))
+    }
+
+    /// list the waiting entries of a resource
+    async fn list_waitlist(
+        &self,
+        request: Request<ListWaitlistRequest>,
+    ) -> Result<Response<ListWaitlistResponse>, Status> {
+        let entries = self.manager.list_waitlist(request.into_inner()).await?;
+        Ok(Response::new(ListWaitlistResponse { entries }))
+    }
+
     /// Server streaming response type for the query method.
     type queryStream = ReservationStream;
     /// get a reservation by id
@@ -109,29 +331,43 @@ impl ReservationService for RsvpService {
     }
 
     /// Server streaming response type for the listen method.
-    type listenStream = ReservationStream;
+    type listenStream = ListenStream;
     /// another system could monitor newly added/confirmed/cancelled reservations
     async fn listen(
         &self,
-        _request: Request<ListenRequest>,
+        request: Request<ListenRequest>,
     ) -> std::result::Result<Response<Self::listenStream>, Status> {
-        todo!()
+        let request = request.into_inner();
+        let changes = self.manager.listen(request).await;
+        let stream = TonicReceiverStream::new(changes);
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    /// acknowledge processed changes, so a named consumer can resume after reconnecting
+    async fn ack(&self, request: Request<AckRequest>) -> Result<Response<AckResponse>, Status> {
+        let request = request.into_inner();
+        let consumer_id = request.consumer_id.clone();
+        let change_id = self.manager.ack(request).await?;
+        Ok(Response::new(AckResponse {
+            consumer_id,
+            change_id,
+        }))
     }
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     use abi::Reservation;
     // use sqlx::migrate::Migrator;
     use crate::test_utils::TestConfig;
+    use reservation::Catalog;
 
     #[tokio::test]
     async fn rpc_reserve_should_work() {
         let config = TestConfig::new();
 
-        let service = RsvpService::from_config(&config).await.unwrap();
+        let service = make_service(&config).await;
         let reservation = Reservation::new_pending(
             "tyr",
             "ixia-3230",
@@ -139,9 +375,7 @@ mod tests {
             "2022-12-30T12:00:00-0700".parse().unwrap(),
             "test device reservation",
         );
-        let request = tonic::Request::new(ReserveRequest {
-            reservation: Some(reservation.clone()),
-        });
+        let request = tonic::Request::new(ReserveRequest::new(reservation.clone()));
 
         let response = service.reserve(request).await.unwrap();
         let reservation1 = response.into_inner().reservation;
@@ -156,4 +390,306 @@ mod tests {
 
         // TestConfig Dropped here -- db dropped
     }
+
+    #[tokio::test]
+    async fn rpc_reserve_conflict_should_suggest_windows() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let request = ReserveRequest::new(reservation.clone());
+        service.reserve(tonic::Request::new(request)).await.unwrap();
+
+        let request =
+            ReserveRequest::new_with_suggestions(reservation.clone(), 3, vec!["ixia-3231".into()]);
+        let status = service
+            .reserve(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        let detail = ReservationConflictDetail::from_status(&status).unwrap();
+        assert_eq!(detail.new.unwrap().start, reservation.start);
+        assert_eq!(detail.suggestions.len(), 3);
+        assert_eq!(detail.suggestions[0].resource_id, "ixia-3231");
+        assert_eq!(detail.suggestions[0].start, reservation.start);
+        assert!(abi::ReservationConflict::from_status(&status).is_some());
+    }
+
+    #[tokio::test]
+    async fn rpc_reserve_conflict_should_survive_failed_suggestions() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let request = ReserveRequest::new(reservation.clone());
+        service.reserve(tonic::Request::new(request)).await.unwrap();
+
+        // an empty sibling id fails the lookup of the suggestions, not the conflict
+        let request = ReserveRequest::new_with_suggestions(reservation, 3, vec!["".into()]);
+        let status = service
+            .reserve(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+        let detail = ReservationConflictDetail::from_status(&status).unwrap();
+        assert!(detail.suggestions.is_empty());
+        assert!(abi::ReservationConflict::from_status(&status).is_some());
+    }
+
+    #[tokio::test]
+    async fn rpc_reschedule_without_window_should_be_invalid() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let request = RescheduleRequest {
+            id: 1,
+            start: None,
+            end: Some("2022-12-26T16:00:00-0700".parse().unwrap()),
+        };
+        let status = service
+            .reschedule(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        assert_eq!(status.message(), abi::Error::InvalidTime.to_string());
+    }
+
+    #[tokio::test]
+    async fn rpc_reserve_any_should_pick_free_member() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let group = abi::ResourceGroup::new("ixias", "Ixias", ["ixia-3230", "ixia-3231"]);
+        service.manager.create_resource_group(group).await.unwrap();
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+
+        for rid in ["ixia-3230", "ixia-3231"] {
+            let request =
+                ReserveAnyRequest::new(reservation.clone(), "ixias", SelectionStrategy::FirstFit);
+            let reserved = service
+                .reserve_any(tonic::Request::new(request))
+                .await
+                .unwrap()
+                .into_inner()
+                .reservation
+                .unwrap();
+            assert_eq!(reserved.resource_id, rid);
+        }
+
+        let request = ReserveAnyRequest::new(reservation, "ixias", SelectionStrategy::FirstFit);
+        let status = service
+            .reserve_any(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn rpc_waitlist_should_queue_and_leave() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let entry = abi::WaitlistEntry::new(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let status = service
+            .join_waitlist(tonic::Request::new(JoinWaitlistRequest::new(entry.clone())))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let reservation = Reservation::new_pending(
+            "alice",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let request = ReserveRequest::new(reservation);
+        service.reserve(tonic::Request::new(request)).await.unwrap();
+        let joined = service
+            .join_waitlist(tonic::Request::new(JoinWaitlistRequest::new(entry.clone())))
+            .await
+            .unwrap()
+            .into_inner()
+            .entry
+            .unwrap();
+        assert_eq!(
+            joined,
+            abi::WaitlistEntry {
+                id: joined.id,
+                ..entry.clone()
+            }
+        );
+
+        let request =
+            ListWaitlistRequest::new("ixia-3230", entry.start.unwrap(), entry.end.unwrap());
+        let entries = service
+            .list_waitlist(tonic::Request::new(request))
+            .await
+            .unwrap()
+            .into_inner()
+            .entries;
+        assert_eq!(entries, vec![joined.clone()]);
+
+        let request = LeaveWaitlistRequest::new(joined.id);
+        service
+            .leave_waitlist(tonic::Request::new(request.clone()))
+            .await
+            .unwrap();
+        let status = service
+            .leave_waitlist(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn rpc_blackout_should_require_admin_token() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let blackout = Reservation::new_blackout(
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "under repair",
+        );
+        let request = BlackoutRequest::new(blackout, BlackoutPolicy::Reject);
+
+        for token in [None, Some("Bearer not-the-admin")] {
+            let mut request = tonic::Request::new(request.clone());
+            if let Some(token) = token {
+                request
+                    .metadata_mut()
+                    .insert("authorization", token.parse().unwrap());
+            }
+            let status = service.blackout(request).await.unwrap_err();
+            assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        }
+
+        let response = service.blackout(admin(request)).await.unwrap().into_inner();
+        assert!(response.blackout.unwrap().is_blackout());
+    }
+
+    #[tokio::test]
+    async fn rpc_blackout_changes_should_require_admin_token() {
+        let config = TestConfig::new();
+
+        let service = make_service(&config).await;
+        let blackout = Reservation::new_blackout(
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "under repair",
+        );
+        let request = BlackoutRequest::new(blackout, BlackoutPolicy::Reject);
+        let blackout = service
+            .blackout(admin(request))
+            .await
+            .unwrap()
+            .into_inner()
+            .blackout
+            .unwrap();
+        let id = blackout.id;
+
+        let status = service
+            .cancel(tonic::Request::new(CancelRequest::new(id)))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        let request = TransitionRequest::new(id, ReservationStatus::Cancelled);
+        let status = service
+            .transition(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        let request = RescheduleRequest::new(
+            id,
+            "2022-12-27T15:00:00-0700".parse().unwrap(),
+            "2022-12-27T16:00:00-0700".parse().unwrap(),
+        );
+        let status = service
+            .reschedule(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        let request = TransferRequest::new(id, "ixia-3231");
+        let status = service
+            .transfer(tonic::Request::new(request))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::PermissionDenied);
+        assert_eq!(service.manager.get(id).await.unwrap(), blackout);
+
+        // the admin still can, and other reservations need no token
+        let cancelled = service
+            .cancel(admin(CancelRequest::new(id)))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+        assert_eq!(cancelled.status, ReservationStatus::Cancelled as i32);
+        let reservation = Reservation::new_pending(
+            "tyr",
+            "ixia-3230",
+            "2022-12-26T15:00:00-0700".parse().unwrap(),
+            "2022-12-26T16:00:00-0700".parse().unwrap(),
+            "test device reservation",
+        );
+        let request = ReserveRequest::new(reservation);
+        let reservation = service
+            .reserve(tonic::Request::new(request))
+            .await
+            .unwrap()
+            .into_inner()
+            .reservation
+            .unwrap();
+        service
+            .cancel(tonic::Request::new(CancelRequest::new(reservation.id)))
+            .await
+            .unwrap();
+    }
+
+    /// request carrying the admin token of the test config
+    fn admin<T>(message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer admin-secret".parse().unwrap());
+        request
+    }
+
+    /// service with the resources the tests reserve on in its catalog
+    async fn make_service(config: &TestConfig) -> RsvpService {
+        let service = RsvpService::from_config(config).await.unwrap();
+        for id in ["ixia-3230", "ixia-3231"] {
+            let resource = abi::Resource::new(id, id, "device");
+            service.manager.create_resource(resource).await.unwrap();
+        }
+        service
+    }
 }
@@ -19,7 +19,6 @@ impl Deref for TestConfig {
 
 impl TestConfig {
     #[allow(dead_code)]
-    
     pub fn new() -> Self {
         let mut config = Config::load("fixtures/config.yml").unwrap();
         
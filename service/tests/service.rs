@@ -2,8 +2,11 @@
 mod test_utils;
 
 use abi::{
-    reservation_service_client::ReservationServiceClient, ConfirmRequest,
-    Reservation, ReservationStatus, ReserveRequest, Config, QueryRequest, ReservationQueryBuilder, ReservationFilterBuilder, FilterResponse, FilterRequest,
+    reservation_service_client::ReservationServiceClient,
+    resource_service_client::ResourceServiceClient, Config, ConfirmRequest, CreateResourceRequest,
+    FilterRequest, FilterResponse, ListenRequest, QueryRequest, Reservation,
+    ReservationFilterBuilder, ReservationQueryBuilder, ReservationStatus, ReservationUpdateType,
+    ReserveRequest, Resource,
 };
 use futures::StreamExt;
 use reservation_service::start_server;
@@ -32,6 +35,9 @@ async fn grpc_server_should_work() {
         .into_inner().reservation.unwrap();
 
     rsvp.id = ret.id;
+    // resources without buffers block the requested window
+    rsvp.blocked_start = rsvp.start.clone();
+    rsvp.blocked_end = rsvp.end.clone();
     assert_eq!(ret, rsvp);
 
     //then we try to make a conflicting reservation
@@ -162,6 +168,28 @@ async fn grpc_filter_should_work() {
     assert_eq!(reservations.len(), 5);
 }
 
+#[tokio::test]
+async fn grpc_listen_should_work() {
+    let tconfig = TestConfig::with_server_port(50020);
+    let mut client = get_test_client(&tconfig).await;
+
+    let mut changes = client
+        .listen(ListenRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+
+    make_reservation(&mut client, 1).await;
+
+    let change = time::timeout(Duration::from_secs(5), changes.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    assert_eq!(change.op, ReservationUpdateType::Create as i32);
+    assert_eq!(change.reservation.unwrap().resource_id, "router-0");
+}
+
 async fn get_test_client(tconfig: &TestConfig) -> ReservationServiceClient<Channel> {
     let config = &tconfig.config;
     setup_server(config);
@@ -181,7 +209,24 @@ async fn get_test_client(tconfig: &TestConfig) -> ReservationServiceClient<Chann
             .await.unwrap()
     };
 
-    time::timeout(Duration::from_secs(5), fut).await.unwrap()
+    let client = time::timeout(Duration::from_secs(5), fut).await.unwrap();
+    seed_resources(config).await;
+    client
+}
+
+/// reservations can only be made on resources of the catalog
+async fn seed_resources(config: &Config) {
+    let mut client = ResourceServiceClient::connect(config.server.url(false))
+        .await
+        .unwrap();
+    let ids = (0..100).map(|i| format!("router-{}", i));
+    for id in ids.chain(["ixia-3230".to_string()]) {
+        let resource = Resource::new(id.clone(), id, "device");
+        client
+            .create_resource(CreateResourceRequest::new(resource))
+            .await
+            .unwrap();
+    }
 }
 
 fn setup_server(config: &Config) {
@@ -200,7 +245,7 @@ async fn make_reservation(client: &mut ReservationServiceClient<Channel>, count:
             format!("router-{}", i),
             "2022-12-26T15:00:00-0700".parse().unwrap(),
             "2022-12-30T12:00:00-0700".parse().unwrap(),
-            &format!("test device reservation {}", i),
+            format!("test device reservation {}", i),
         );
         let ret = client
             .reserve(ReserveRequest::new(rsvp.clone()))
@@ -209,6 +254,8 @@ async fn make_reservation(client: &mut ReservationServiceClient<Channel>, count:
             .into_inner().reservation.unwrap();
 
         rsvp.id = ret.id;
+        rsvp.blocked_start = rsvp.start.clone();
+        rsvp.blocked_end = rsvp.end.clone();
         assert_eq!(ret, rsvp);
     }
 }
\ No newline at end of file